@@ -3,12 +3,11 @@
 
 use std::fs::read_dir;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Ok, Result};
-use instant::Instant;
 use vello::Scene;
 use vello::kurbo::Vec2;
-use vello_svg::usvg;
 
 use crate::{ExampleScene, SceneParams, SceneSet};
 
@@ -91,24 +90,33 @@ pub fn svg_function_of<R: AsRef<str>>(
     name: String,
     contents: impl FnOnce() -> R + Send + 'static,
 ) -> impl FnMut(&mut Scene, &mut SceneParams) {
-    fn render_svg_contents(name: &str, contents: &str) -> (Scene, Vec2) {
-        let start = Instant::now();
-        let svg = usvg::Tree::from_str(contents, &usvg::Options::default())
-            .unwrap_or_else(|e| panic!("failed to parse svg file {name}: {e}"));
-        eprintln!("Parsed svg {name} in {:?}", start.elapsed());
-        let start = Instant::now();
-        let scene = vello_svg::render_tree(&svg);
-        let resolution = Vec2::new(svg.size().width() as f64, svg.size().height() as f64);
-        eprintln!("Encoded svg {name} in {:?}", start.elapsed());
-        (scene, resolution)
+    fn print_stats(name: &str, rendered: &vello_svg::RenderedSvg) {
+        let stats = &rendered.stats;
+        let time = |time: Option<Duration>| time.map_or("-".to_string(), |t| format!("{t:?}"));
+        eprintln!(
+            "Rendered svg {name}: parsed in {}, encoded in {}; \
+            {} paths ({} segments), {} layers (max depth {}), {} images ({} bytes)",
+            time(stats.parse_time),
+            time(stats.encode_time),
+            stats.paths,
+            stats.path_segments,
+            stats.layers,
+            stats.max_layer_depth,
+            stats.images,
+            stats.decoded_image_bytes,
+        );
+    }
+    fn finish(
+        name: &str,
+        result: Result<vello_svg::RenderedSvg, vello_svg::Error>,
+    ) -> (Scene, Vec2) {
+        let rendered = result.unwrap_or_else(|e| panic!("failed to parse svg file {name}: {e}"));
+        print_stats(name, &rendered);
+        (rendered.scene, rendered.size.to_vec2())
     }
     let mut cached_scene = None;
     #[cfg(not(target_arch = "wasm32"))]
-    let (tx, rx) = std::sync::mpsc::channel();
-    #[cfg(not(target_arch = "wasm32"))]
-    let mut tx = Some(tx);
-    #[cfg(not(target_arch = "wasm32"))]
-    let mut has_started_parse = false;
+    let mut task: Option<vello_svg::RenderTask> = None;
     let mut contents = Some(contents);
     move |scene, params| {
         if let Some((scene_frag, resolution)) = cached_scene.as_mut() {
@@ -119,7 +127,7 @@ pub fn svg_function_of<R: AsRef<str>>(
         if cfg!(target_arch = "wasm32") || !params.interactive {
             let contents = contents.take().unwrap();
             let contents = contents();
-            let (scene_frag, resolution) = render_svg_contents(&name, contents.as_ref());
+            let (scene_frag, resolution) = finish(&name, vello_svg::render(contents.as_ref()));
             scene.append(&scene_frag, None);
             params.resolution = Some(resolution);
             cached_scene = Some((scene_frag, resolution));
@@ -128,39 +136,36 @@ pub fn svg_function_of<R: AsRef<str>>(
         }
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let mut timeout = std::time::Duration::from_millis(10);
-            if !has_started_parse {
-                has_started_parse = true;
+            let mut timeout = Duration::from_millis(10);
+            let task = task.get_or_insert_with(|| {
                 // Prefer jank over loading screen for first time
-                timeout = std::time::Duration::from_millis(75);
-                let tx = tx.take().unwrap();
+                timeout = Duration::from_millis(75);
                 let contents = contents.take().unwrap();
-                let name = name.clone();
-                std::thread::spawn(move || {
-                    let contents = contents();
-                    tx.send(render_svg_contents(&name, contents.as_ref()))
-                        .unwrap();
-                });
-            }
-            let recv = rx.recv_timeout(timeout);
-            use std::sync::mpsc::RecvTimeoutError;
-            match recv {
-                Result::Ok((scene_frag, resolution)) => {
+                // Read the file on the background thread too.
+                vello_svg::render_async(
+                    move || {
+                        let svg = contents().as_ref().as_bytes().to_owned();
+                        std::result::Result::<_, vello_svg::Error>::Ok(svg)
+                    },
+                    vello_svg::usvg::Options::default(),
+                    vello_svg::RenderOptions::default(),
+                )
+            });
+            match task.take_timeout(timeout) {
+                Some(result) => {
+                    let (scene_frag, resolution) = finish(&name, result);
                     scene.append(&scene_frag, None);
                     params.resolution = Some(resolution);
                     cached_scene = Some((scene_frag, resolution));
                 }
-                Err(RecvTimeoutError::Timeout) => params.text.add(
+                None => params.text.add(
                     scene,
                     None,
                     48.,
                     None,
                     vello::kurbo::Affine::translate((110.0, 600.0)),
-                    &format!("Loading {name}"),
+                    &format!("Loading {name} ({:.0}%)", task.progress() * 100.0),
                 ),
-                Err(RecvTimeoutError::Disconnected) => {
-                    panic!()
-                }
             }
         };
     }
@@ -0,0 +1,237 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::stats::Timer;
+use crate::{
+    DefaultErrorHandler, Error, ErrorHandler, PaintOverride, RenderOptions, RenderedSvg,
+    UnsupportedNode, util,
+};
+use std::sync::Arc;
+use vello::Scene;
+use vello::kurbo::Affine;
+use vello::peniko::Color;
+use vello::peniko::color::{DynamicColor, palette};
+
+/// The color `currentColor` is resolved to while parsing, to find the paints which use it.
+const SENTINEL: usvg::Color = usvg::Color {
+    red: 0x0b,
+    green: 0xad,
+    blue: 0xc0,
+};
+
+/// An SVG document, such as an icon, whose `currentColor` can be changed after it was encoded.
+///
+/// usvg resolves `currentColor` while parsing, so this parses the document with the `color` of
+/// its root element set to a placeholder, and finds where the encoded fills, strokes and gradient
+/// stops use it. Changing the color then only patches those colors in the encoding, without
+/// parsing or encoding the document again.
+///
+/// Colors set by the document itself on descendants of the root element still apply, as does a
+/// color set on the root element by a style sheet. Paints which happen to use the placeholder
+/// color `#0badc0` are also changed.
+#[derive(Debug, Clone)]
+pub struct CurrentColorSvg {
+    rendered: RenderedSvg,
+    color: Color,
+    /// The indices of the solid colors in the draw data which use `currentColor`, and their alpha.
+    solid: Vec<(usize, f32)>,
+    /// The indices of the gradient stops which use `currentColor`, and their alpha.
+    stops: Vec<(usize, f32)>,
+}
+
+/// Replaces the placeholder color with its color, keeping the alpha of the paint.
+#[derive(Debug)]
+struct Probe(Color);
+
+impl PaintOverride for Probe {
+    fn map_color(&self, color: Color) -> Color {
+        let rgba = color.to_rgba8();
+        if (rgba.r, rgba.g, rgba.b) != (SENTINEL.red, SENTINEL.green, SENTINEL.blue) {
+            return color;
+        }
+        self.0.multiply_alpha(color.components[3])
+    }
+}
+
+/// Passes unsupported nodes on to an error handler, and keeps what it drew to draw it again.
+struct Record<'a, H: ?Sized> {
+    error_handler: &'a mut H,
+    drawn: Vec<Scene>,
+}
+
+impl<H: ErrorHandler + ?Sized> ErrorHandler for Record<'_, H> {
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        let mut drawn = Scene::new();
+        self.error_handler.on_unsupported(&mut drawn, unsupported)?;
+        scene.append(&drawn, None);
+        self.drawn.push(drawn);
+        Ok(())
+    }
+}
+
+/// Draws what a [`Record`] kept again, in the same order, without calling the error handler.
+struct Replay(std::vec::IntoIter<Scene>);
+
+impl ErrorHandler for Replay {
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        _unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        if let Some(drawn) = self.0.next() {
+            scene.append(&drawn, None);
+        }
+        Ok(())
+    }
+}
+
+impl CurrentColorSvg {
+    /// Parse and encode an SVG document with default error handling, with black as its current
+    /// color.
+    ///
+    /// This will draw a red box over (some) unsupported elements.
+    pub fn new(svg: &str) -> Result<Self, Error> {
+        Self::with_handler(svg, &usvg::Options::default(), &mut DefaultErrorHandler)
+    }
+
+    /// Parse and encode an SVG document with an [`ErrorHandler`], with black as its current
+    /// color.
+    ///
+    /// The document is encoded twice, to find the colors which use `currentColor`, but the error
+    /// handler is only called once for each unsupported node.
+    pub fn with_handler<H: ErrorHandler + ?Sized>(
+        svg: &str,
+        opt: &usvg::Options<'_>,
+        error_handler: &mut H,
+    ) -> Result<Self, Error> {
+        let timer = Timer::start();
+        let svg = with_placeholder_color(svg)?;
+        let parsed = crate::parse(&svg, opt)?;
+        let parse_time = timer.elapsed();
+
+        let encode = |color: Color, error_handler: &mut dyn ErrorHandler| {
+            let options = RenderOptions {
+                paint_override: Some(Arc::new(Probe(color))),
+                ..RenderOptions::default()
+            };
+            crate::render_tree_inner(&parsed.tree, parsed.view_box, &options, error_handler)
+        };
+        let mut record = Record {
+            error_handler,
+            drawn: Vec::new(),
+        };
+        let (mut rendered, result) = encode(palette::css::BLACK, &mut record);
+        result?;
+        // Only the colors which use `currentColor` differ between the encodings. They are white
+        // in the second one, which is premultiplied to their alpha in each channel.
+        let (probe, _) = encode(palette::css::WHITE, &mut Replay(record.drawn.into_iter()));
+        let (black, white) = (rendered.scene.encoding(), probe.scene.encoding());
+        debug_assert_eq!(
+            (black.draw_data.len(), black.resources.color_stops.len()),
+            (white.draw_data.len(), white.resources.color_stops.len()),
+            "colors don't change how paints are encoded"
+        );
+        let solid = black
+            .draw_data
+            .iter()
+            .zip(&white.draw_data)
+            .enumerate()
+            .filter(|(_, (black, white))| black != white)
+            .map(|(index, (_, white))| (index, (white & 0xff) as f32 / 255.0))
+            .collect();
+        let stops = black
+            .resources
+            .color_stops
+            .iter()
+            .zip(&white.resources.color_stops)
+            .enumerate()
+            .filter(|(_, (black, white))| black.color != white.color)
+            .map(|(index, (_, white))| (index, white.color.components[3]))
+            .collect();
+
+        rendered.stats.parse_time = parse_time;
+        Ok(Self {
+            rendered,
+            color: palette::css::BLACK,
+            solid,
+            stops,
+        })
+    }
+
+    /// The encoded document, drawn with the current color.
+    pub fn rendered(&self) -> &RenderedSvg {
+        &self.rendered
+    }
+
+    /// The color `currentColor` is drawn with.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Change the color `currentColor` is drawn with.
+    ///
+    /// The alpha of `color` is multiplied with the opacity of each paint.
+    pub fn set_color(&mut self, color: Color) {
+        if color == self.color {
+            return;
+        }
+        self.color = color;
+        let encoding = self.rendered.scene.encoding_mut();
+        for &(index, alpha) in &self.solid {
+            // Solid colors are encoded premultiplied, see `vello_encoding::DrawColor`.
+            encoding.draw_data[index] = color
+                .multiply_alpha(alpha)
+                .premultiply()
+                .to_rgba8()
+                .to_u32();
+        }
+        for &(index, alpha) in &self.stops {
+            encoding.resources.color_stops[index].color =
+                DynamicColor::from_alpha_color(color.multiply_alpha(alpha));
+        }
+    }
+
+    /// Append the document to `scene` with `transform` applied, with `color` as its current
+    /// color.
+    pub fn append_to(&mut self, scene: &mut Scene, transform: Affine, color: Color) {
+        self.set_color(color);
+        scene.append(&self.rendered.scene, Some(transform));
+    }
+}
+
+/// Set the `color` of the root element of `svg` to the placeholder, by only rewriting its start
+/// tag.
+fn with_placeholder_color(svg: &str) -> Result<String, Error> {
+    let xml_opt = usvg::roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = usvg::roxmltree::Document::parse_with_options(svg, xml_opt)
+        .map_err(usvg::Error::ParsingFailed)?;
+    let root = doc.root_element();
+    let placeholder = format!(
+        "#{:02x}{:02x}{:02x}",
+        SENTINEL.red, SENTINEL.green, SENTINEL.blue
+    );
+    let color = root
+        .attributes()
+        .find(|attribute| attribute.namespace().is_none() && attribute.name() == "color");
+    let (range, value) = match color {
+        Some(attribute) => (attribute.range_value(), placeholder),
+        None => {
+            // Insert the attribute after the name of the element.
+            let name = util::start_tag_name(&svg[root.range()]);
+            let end = root.range().start + 1 + name.len();
+            (end..end, format!(r#" color="{placeholder}""#))
+        }
+    };
+    let mut source = String::with_capacity(svg.len() + value.len());
+    source.push_str(&svg[..range.start]);
+    source.push_str(&value);
+    source.push_str(&svg[range.end..]);
+    Ok(source)
+}
@@ -0,0 +1,127 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::util;
+use vello::Scene;
+use vello::kurbo::{Affine, Rect, Shape, Stroke};
+use vello::peniko::color::palette;
+use vello::peniko::{Color, Fill};
+
+/// Settings for a debug overlay, drawn on top of a rendered document to show how it was traversed.
+///
+/// Line widths are in the coordinate space of the scene the document is rendered to.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct DebugOverlay {
+    /// Outline the bounding box of each node, colored by the number of layers it is nested in.
+    pub bounds: bool,
+    /// Outline the clip path of each group which has one.
+    pub clip_paths: bool,
+    /// Highlight the nodes which were passed to the error handler.
+    pub unsupported: bool,
+    /// Label each node which has an id, by calling this with the scene to draw the label into,
+    /// the id, and the bounding box of the node in the coordinate space of the scene.
+    ///
+    /// Labels are drawn on top of the rest of the overlay. This crate doesn't draw text itself,
+    /// so this is where an application draws ids with its own text renderer, or logs them.
+    pub ids: Option<fn(&mut Scene, &str, Rect)>,
+    /// The width of outlines.
+    pub line_width: f64,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self {
+            bounds: true,
+            clip_paths: true,
+            unsupported: true,
+            ids: None,
+            line_width: 1.0,
+        }
+    }
+}
+
+/// Colors for layer nesting depths, cycled through for deeper nesting.
+const DEPTH_COLORS: [Color; 6] = [
+    palette::css::DODGER_BLUE,
+    palette::css::LIME_GREEN,
+    palette::css::ORANGE,
+    palette::css::MEDIUM_PURPLE,
+    palette::css::GOLD,
+    palette::css::TURQUOISE,
+];
+
+/// Accumulates the debug overlay while a document is traversed.
+pub(crate) struct Overlay {
+    settings: DebugOverlay,
+    scene: Scene,
+    labels: Scene,
+}
+
+impl Overlay {
+    pub(crate) fn new(settings: DebugOverlay) -> Self {
+        Self {
+            settings,
+            scene: Scene::new(),
+            labels: Scene::new(),
+        }
+    }
+
+    /// Record a node, nested inside of `depth` layers.
+    pub(crate) fn node(&mut self, node: &usvg::Node, transform: Affine, depth: usize) {
+        let bounds = util::to_rect(&node.bounding_box());
+        if self.settings.bounds {
+            let color = DEPTH_COLORS[depth % DEPTH_COLORS.len()];
+            self.outline(&bounds, transform, color, &[]);
+        }
+        if let Some(label) = self.settings.ids
+            && !node.id().is_empty()
+        {
+            label(
+                &mut self.labels,
+                node.id(),
+                transform.transform_rect_bbox(bounds),
+            );
+        }
+    }
+
+    /// Record the clip path used for a layer.
+    pub(crate) fn clip_path(&mut self, clip: &impl Shape, transform: Affine) {
+        if self.settings.clip_paths {
+            let dash = self.settings.line_width * 4.0;
+            self.outline(clip, transform, palette::css::CYAN, &[dash, dash]);
+        }
+    }
+
+    /// Record a node which was passed to the error handler.
+    pub(crate) fn unsupported(&mut self, node: &usvg::Node, transform: Affine) {
+        if self.settings.unsupported {
+            let bounds = transform * util::to_rect(&node.bounding_box()).to_path(0.1);
+            let color = palette::css::MAGENTA;
+            self.scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                color.with_alpha(0.3),
+                None,
+                &bounds,
+            );
+            let stroke = Stroke::new(self.settings.line_width * 2.0);
+            self.scene
+                .stroke(&stroke, Affine::IDENTITY, color, None, &bounds);
+        }
+    }
+
+    /// Draw the overlay on top of `scene`.
+    pub(crate) fn finish(self, scene: &mut Scene) {
+        scene.append(&self.scene, None);
+        scene.append(&self.labels, None);
+    }
+
+    fn outline(&mut self, shape: &impl Shape, transform: Affine, color: Color, dashes: &[f64]) {
+        // Transform the shape rather than the stroke, so that lines have the same width everywhere.
+        let path = transform * shape.to_path(0.1);
+        let stroke = Stroke::new(self.settings.line_width).with_dashes(0.0, dashes.iter().copied());
+        self.scene
+            .stroke(&stroke, Affine::IDENTITY, color, None, &path);
+    }
+}
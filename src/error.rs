@@ -3,10 +3,75 @@
 
 use thiserror::Error;
 
-/// Triggered when there is an issue parsing user input.
+/// Triggered when there is an issue parsing user input, or when rendering in strict mode fails.
+///
+/// The variants for rendering failures carry the id of the offending node, which is empty if the
+/// node has no id.
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum Error {
     #[error("Error parsing svg: {0}")]
-    Svg(#[from] usvg::Error),
+    Svg(usvg::Error),
+    #[error("Error reading svg: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Error decompressing svgz data")]
+    Decompression,
+    #[error("Image `{id}` could not be decoded")]
+    UndecodableImage { id: String },
+    #[error("Node `{id}` uses an unsupported paint server")]
+    UnsupportedPaint { id: String },
+    #[error("Group `{id}` uses unsupported filters")]
+    UnsupportedFilter { id: String },
+    #[error("Group `{id}` uses an unsupported mask")]
+    UnsupportedMask { id: String },
+    #[error("Group `{id}` uses an unsupported clip path")]
+    UnsupportedClipPath { id: String },
+    #[error("No node has the id `{id}`")]
+    NodeNotFound { id: String },
+    #[error("Node `{id}` exceeds the {limit} limit")]
+    LimitExceeded { id: String, limit: Limit },
+    #[error("Rendering was cancelled")]
+    Cancelled,
+}
+
+/// A limit on the resources used while rendering a document, see [`Error::LimitExceeded`]
+/// and [`Limits`](crate::Limits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Limit {
+    /// The number of nested groups, nested SVG images and text nodes.
+    NestingDepth,
+    /// The number of nested SVG images.
+    NestedSvgDepth,
+    /// The number of nodes.
+    Nodes,
+    /// The number of path segments.
+    PathSegments,
+    /// The number of pixels in a raster image.
+    ImagePixels,
+    /// The total size of the decoded raster images.
+    DecodedBytes,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NestingDepth => "nesting depth",
+            Self::NestedSvgDepth => "nested SVG depth",
+            Self::Nodes => "node count",
+            Self::PathSegments => "path segment count",
+            Self::ImagePixels => "image pixel count",
+            Self::DecodedBytes => "decoded image size",
+        })
+    }
+}
+
+impl From<usvg::Error> for Error {
+    fn from(err: usvg::Error) -> Self {
+        match err {
+            usvg::Error::MalformedGZip => Self::Decompression,
+            err => Self::Svg(err),
+        }
+    }
 }
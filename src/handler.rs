@@ -0,0 +1,143 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{Error, util};
+use vello::Scene;
+use vello::kurbo::Affine;
+use vello::peniko::Fill;
+use vello::peniko::color::palette;
+
+/// The reason a node can't be drawn exactly as specified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Unsupported {
+    /// A fill or stroke uses a pattern. The fill or stroke is not drawn.
+    PatternPaint,
+    /// A raster image could not be decoded. The image is not drawn.
+    UndecodableImage,
+    /// A raster image can't be decoded because the `image` feature is disabled.
+    /// The image is not drawn.
+    MissingImageFeature,
+    /// A group has a mask, which is ignored.
+    Mask,
+    /// A group has filters, which are ignored.
+    Filter,
+    /// A group's clip path is more complex than a single untransformed path.
+    /// The first path in the clip path is used if there is one, otherwise the bounding box
+    /// of the group is used.
+    ClipPath,
+}
+
+impl Unsupported {
+    /// Whether the node (or part of it) is left out entirely, rather than drawn approximately.
+    pub fn is_omitted(self) -> bool {
+        match self {
+            Self::PatternPaint | Self::UndecodableImage | Self::MissingImageFeature => true,
+            Self::Mask | Self::Filter | Self::ClipPath => false,
+        }
+    }
+}
+
+/// A node which can't be drawn exactly, passed to an [`ErrorHandler`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct UnsupportedNode<'a> {
+    /// Why the node can't be drawn exactly.
+    pub reason: Unsupported,
+    /// The node itself.
+    pub node: &'a usvg::Node,
+    /// The groups containing the node, starting with the root group of the document.
+    ///
+    /// For nodes inside of nested SVG images or text, this includes the root group of the nested
+    /// document or the flattened text group.
+    pub ancestors: &'a [&'a usvg::Group],
+    /// The transform from the coordinate space of the node to that of the scene.
+    pub transform: Affine,
+}
+
+impl UnsupportedNode<'_> {
+    /// The error which [`StrictErrorHandler`] returns for this node.
+    pub fn to_error(&self) -> Error {
+        let id = self.node.id().to_owned();
+        match self.reason {
+            Unsupported::PatternPaint => Error::UnsupportedPaint { id },
+            Unsupported::UndecodableImage | Unsupported::MissingImageFeature => {
+                Error::UndecodableImage { id }
+            }
+            Unsupported::Mask => Error::UnsupportedMask { id },
+            Unsupported::Filter => Error::UnsupportedFilter { id },
+            Unsupported::ClipPath => Error::UnsupportedClipPath { id },
+        }
+    }
+}
+
+/// Handles nodes which can't be drawn exactly while rendering a document.
+///
+/// Closures of the form `FnMut(&mut Scene, &usvg::Node)` implement this trait. They are only
+/// called for nodes which are [omitted](Unsupported::is_omitted), and never fail.
+pub trait ErrorHandler {
+    /// Called for each node which can't be drawn exactly, at the point in the traversal where
+    /// it would have been drawn.
+    ///
+    /// Returning an error stops rendering, and is passed on to the caller.
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error>;
+}
+
+impl<F: FnMut(&mut Scene, &usvg::Node)> ErrorHandler for F {
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        if unsupported.reason.is_omitted() {
+            self(scene, unsupported.node);
+        }
+        Ok(())
+    }
+}
+
+/// Error handler which draws a transparent red box over omitted nodes, and ignores
+/// approximated ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultErrorHandler;
+
+impl ErrorHandler for DefaultErrorHandler {
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        if unsupported.reason.is_omitted() {
+            let rect = util::to_rect(&unsupported.node.bounding_box());
+            scene.fill(
+                Fill::NonZero,
+                unsupported.transform,
+                palette::css::RED.with_alpha(0.5),
+                None,
+                &rect,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Error handler which fails on the first node which can't be drawn exactly.
+///
+/// The returned error is [`UnsupportedNode::to_error`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictErrorHandler;
+
+impl ErrorHandler for StrictErrorHandler {
+    fn on_unsupported(
+        &mut self,
+        _scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        Err(unsupported.to_error())
+    }
+}
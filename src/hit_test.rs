@@ -0,0 +1,200 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::util;
+use vello::kurbo::{Affine, BezPath, Point, Shape, StrokeOpts};
+
+/// The tolerance used to flatten curves when testing strokes, in the coordinate space of the node.
+const STROKE_TOLERANCE: f64 = 0.01;
+
+/// A node found by [`hit_test`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NodeRef<'a> {
+    /// The node which was hit.
+    ///
+    /// For text, this is the text node rather than one of the paths it is drawn with.
+    pub node: &'a usvg::Node,
+    /// The groups containing the node, starting with the root group of the document.
+    pub ancestors: Vec<&'a usvg::Group>,
+    /// The part of the node which was hit.
+    pub part: HitPart,
+}
+
+/// The part of a node which was hit, see [`NodeRef`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HitPart {
+    /// The fill of a path or text.
+    Fill,
+    /// The stroke of a path or text.
+    Stroke,
+    /// A raster or SVG image.
+    Image,
+}
+
+/// Find the topmost node of a document which is drawn at `point`.
+///
+/// `transform` is the transform the document is drawn with, such that `point` is in the
+/// coordinate space of the scene. Fill rules, stroke geometry, clip paths, visibility and paint
+/// order are taken into account, but opacity, masks and filters are not, so a node is hit even
+/// where it is transparent.
+///
+/// Images are hit anywhere inside of their bounds, and the content of nested SVG images is not
+/// searched.
+pub fn hit_test(svg: &usvg::Tree, point: Point, transform: Affine) -> Option<NodeRef<'_>> {
+    if transform.determinant() == 0.0 {
+        return None;
+    }
+    let point = transform.inverse() * point;
+    let mut ancestors = Vec::new();
+    let (node, part) = hit_group(svg.root(), point, &mut ancestors)?;
+    Some(NodeRef {
+        node,
+        ancestors,
+        part,
+    })
+}
+
+/// Find the topmost descendant of `group` at `point`, which is in the coordinate space of the
+/// document.
+///
+/// On success, `ancestors` holds the groups containing the node.
+fn hit_group<'a>(
+    group: &'a usvg::Group,
+    point: Point,
+    ancestors: &mut Vec<&'a usvg::Group>,
+) -> Option<(&'a usvg::Node, HitPart)> {
+    ancestors.push(group);
+    for node in group.children().iter().rev() {
+        let hit = match node {
+            usvg::Node::Group(g) => {
+                if !util::to_rect(&g.abs_layer_bounding_box().to_rect()).contains(point)
+                    || !in_clip_path(g, point)
+                {
+                    continue;
+                }
+                match hit_group(g, point, ancestors) {
+                    Some(hit) => return Some(hit),
+                    None => continue,
+                }
+            }
+            usvg::Node::Path(path) => hit_path(path, point),
+            usvg::Node::Image(image) => (image.is_visible()
+                && util::to_rect(&image.abs_bounding_box()).contains(point))
+            .then_some(HitPart::Image),
+            usvg::Node::Text(text) => {
+                // Report the text itself rather than the paths it is drawn with.
+                let mut text_ancestors = Vec::new();
+                hit_group(
+                    text.flattened(),
+                    text_point(text, point),
+                    &mut text_ancestors,
+                )
+                .map(|(_, part)| part)
+            }
+        };
+        if let Some(part) = hit {
+            return Some((node, part));
+        }
+    }
+    ancestors.pop();
+    None
+}
+
+fn hit_path(path: &usvg::Path, point: Point) -> Option<HitPart> {
+    if !path.is_visible() || !util::to_rect(&path.abs_stroke_bounding_box()).contains(point) {
+        return None;
+    }
+    let local_point = util::to_affine(&path.abs_transform()).inverse() * point;
+    let local_path = util::to_bez_path(path);
+    let in_fill = || {
+        path.fill()
+            .is_some_and(|fill| contains(&local_path, fill.rule(), local_point))
+    };
+    let in_stroke = || {
+        path.stroke().is_some_and(|stroke| {
+            let outline = vello::kurbo::stroke(
+                local_path.iter(),
+                &util::to_stroke(stroke),
+                &StrokeOpts::default(),
+                STROKE_TOLERANCE,
+            );
+            outline.winding(local_point) != 0
+        })
+    };
+    // Test whichever is drawn on top first.
+    match path.paint_order() {
+        usvg::PaintOrder::FillAndStroke => {
+            if in_stroke() {
+                Some(HitPart::Stroke)
+            } else {
+                in_fill().then_some(HitPart::Fill)
+            }
+        }
+        usvg::PaintOrder::StrokeAndFill => {
+            if in_fill() {
+                Some(HitPart::Fill)
+            } else {
+                in_stroke().then_some(HitPart::Stroke)
+            }
+        }
+    }
+}
+
+/// Whether `point`, in the coordinate space of the document, is inside of the clip path of
+/// `group`, if it has one.
+fn in_clip_path(group: &usvg::Group, point: Point) -> bool {
+    let Some(clip_path) = group.clip_path() else {
+        return true;
+    };
+    // Clip paths are in the coordinate space of the group they clip.
+    let group_point = util::to_affine(&group.abs_transform()).inverse() * point;
+    in_clip_path_content(clip_path, group_point)
+}
+
+fn in_clip_path_content(clip_path: &usvg::ClipPath, point: Point) -> bool {
+    if let Some(clip_path) = clip_path.clip_path()
+        && !in_clip_path_content(clip_path, point)
+    {
+        return false;
+    }
+    let clip_point = util::to_affine(&clip_path.transform()).inverse() * point;
+    in_clip_group(clip_path.root(), clip_point)
+}
+
+/// Whether `point` is inside any of the shapes of a clip path, relative to its root `group`.
+fn in_clip_group(group: &usvg::Group, point: Point) -> bool {
+    group.children().iter().any(|node| match node {
+        usvg::Node::Group(g) => {
+            let group_point = util::to_affine(&g.abs_transform()).inverse() * point;
+            g.clip_path()
+                .is_none_or(|clip_path| in_clip_path_content(clip_path, group_point))
+                && in_clip_group(g, point)
+        }
+        usvg::Node::Path(path) => {
+            let local_point = util::to_affine(&path.abs_transform()).inverse() * point;
+            // The clip rule of a shape is converted to its fill rule.
+            path.is_visible()
+                && path.fill().is_some_and(|fill| {
+                    contains(&util::to_bez_path(path), fill.rule(), local_point)
+                })
+        }
+        usvg::Node::Text(text) => in_clip_group(text.flattened(), text_point(text, point)),
+        usvg::Node::Image(_) => false,
+    })
+}
+
+/// Map `point` into the coordinate space of the flattened group of `text`, whose absolute
+/// transforms don't include the transforms of the ancestors of the text.
+fn text_point(text: &usvg::Text, point: Point) -> Point {
+    util::to_affine(&text.abs_transform()).inverse() * point
+}
+
+fn contains(path: &BezPath, rule: usvg::FillRule, point: Point) -> bool {
+    let winding = path.winding(point);
+    match rule {
+        usvg::FillRule::NonZero => winding != 0,
+        usvg::FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
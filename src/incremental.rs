@@ -0,0 +1,172 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::stats::Timer;
+use crate::{DefaultErrorHandler, Error, RenderOptions, RenderedSvg, render};
+use std::time::Duration;
+use vello::Scene;
+use vello::kurbo::Affine;
+
+/// How much of a document one call to [`IncrementalRender::resume`] may encode.
+///
+/// Each call encodes at least one node, so that the encoding always makes progress. A budget
+/// without limits encodes the rest of the document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Pause once this much time has passed.
+    ///
+    /// Time isn't measured on `wasm32` targets, where this is ignored, so use
+    /// [`nodes`](Self::nodes) instead.
+    pub time: Option<Duration>,
+    /// Pause once this many nodes have been encoded.
+    ///
+    /// Groups count as one node, and their children are counted separately. Paths merged by
+    /// [`RenderOptions::merge_paths`] count as one node together.
+    pub nodes: Option<usize>,
+}
+
+impl Budget {
+    /// A budget which pauses once `time` has passed.
+    pub fn time(time: Duration) -> Self {
+        Self {
+            time: Some(time),
+            nodes: None,
+        }
+    }
+
+    /// A budget which pauses once `nodes` nodes have been encoded.
+    pub fn nodes(nodes: usize) -> Self {
+        Self {
+            time: None,
+            nodes: Some(nodes),
+        }
+    }
+}
+
+/// An SVG document being encoded a few nodes at a time, e.g. over several frames of a UI
+/// without blocking it and without threads.
+///
+/// The scene, report and statistics are identical to those of
+/// [`render_tree_with_options`](crate::render_tree_with_options), except that the encoding
+/// time is the sum of the time spent in each call to [`resume`](Self::resume).
+///
+/// This uses default error handling, so draws a red box over (some) unsupported elements.
+///
+/// ```
+/// # fn main() -> Result<(), vello_svg::Error> {
+/// use vello_svg::{Budget, IncrementalRender, RenderOptions, usvg};
+///
+/// let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="10" height="10"/></svg>"#;
+/// let tree = usvg::Tree::from_str(svg, &usvg::Options::default())?;
+/// let mut render = IncrementalRender::new(&tree, &RenderOptions::default());
+/// // In a UI, this would be called once per frame.
+/// while !render.resume(Budget::nodes(1))? {}
+/// let rendered = render.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct IncrementalRender<'t> {
+    tree: &'t usvg::Tree,
+    scene: Scene,
+    cx: render::Context<'static, 't, DefaultErrorHandler>,
+    transform: Affine,
+    started: bool,
+    finished: bool,
+    /// The number of nodes encoded so far.
+    nodes: usize,
+    encode_time: Option<Duration>,
+}
+
+impl<'t> IncrementalRender<'t> {
+    /// Prepare to encode `tree`, without encoding anything yet.
+    pub fn new(tree: &'t usvg::Tree, options: &RenderOptions) -> Self {
+        let scene = Scene::new();
+        let cx = render::Context::new(&scene, options, DefaultErrorHandler);
+        Self {
+            tree,
+            scene,
+            cx,
+            transform: options.origin_transform(),
+            started: false,
+            finished: false,
+            nodes: 0,
+            encode_time: Some(Duration::ZERO),
+        }
+    }
+
+    /// Encode more of the document, until it is finished or `budget` runs out.
+    ///
+    /// Returns whether the whole document has been encoded, or an error if the document exceeds
+    /// a limit, after which nothing more is encoded.
+    pub fn resume(&mut self, budget: Budget) -> Result<bool, Error> {
+        if self.finished {
+            return Ok(true);
+        }
+        let timer = Timer::start();
+        let result = self.encode(budget, timer);
+        self.encode_time = self.encode_time.zip(timer.elapsed()).map(|(a, b)| a + b);
+        self.finished = !matches!(result, Ok(false));
+        result
+    }
+
+    /// The number of nodes encoded so far, e.g. to report progress.
+    ///
+    /// The descendants of skipped nodes aren't counted.
+    pub(crate) fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Whether the whole document has been encoded, or encoding it failed.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Encode the rest of the document, and return it.
+    ///
+    /// If [`resume`](Self::resume) returned an error, this returns the part of the document
+    /// encoded before it.
+    ///
+    /// Returns an error if the rest of the document exceeds a limit.
+    pub fn finish(mut self) -> Result<RenderedSvg, Error> {
+        self.resume(Budget::default())?;
+        let (report, mut stats) = self.cx.finish(&mut self.scene);
+        stats.encode_time = self.encode_time;
+        Ok(RenderedSvg::new(self.scene, self.tree, None, report, stats))
+    }
+
+    /// Returns whether the document was finished.
+    fn encode(&mut self, budget: Budget, timer: Timer) -> Result<bool, Error> {
+        if !self.started {
+            self.started = true;
+            render::render_group_incremental(
+                &mut self.scene,
+                self.tree.root(),
+                self.transform,
+                &mut self.cx,
+            )?;
+        }
+        let mut nodes = 0;
+        render::resume(&mut self.scene, &mut self.cx, || {
+            let paused = nodes > 0
+                && (budget.nodes.is_some_and(|max| nodes >= max)
+                    || budget
+                        .time
+                        .zip(timer.elapsed())
+                        .is_some_and(|(max, elapsed)| elapsed >= max));
+            nodes += 1;
+            if !paused {
+                self.nodes += 1;
+            }
+            paused
+        })
+    }
+}
+
+impl std::fmt::Debug for IncrementalRender<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncrementalRender")
+            .field("finished", &self.finished)
+            .field("encode_time", &self.encode_time)
+            .finish_non_exhaustive()
+    }
+}
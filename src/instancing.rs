@@ -0,0 +1,194 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::collections::HashMap;
+
+/// The groups of a document which have the same content as other groups, e.g. because they
+/// were expanded from `<use>` elements referencing the same element, see
+/// [`RenderOptions::instancing`](crate::RenderOptions::instancing).
+///
+/// Each distinct subtree gets a class, found by interning a description of each node which
+/// refers to its children by their classes, so subtrees are only in the same class if they are
+/// exactly the same, except for the transform of their root.
+pub(crate) struct Instances {
+    /// The class of each group, by its address.
+    groups: HashMap<usize, usize>,
+    /// The number of groups in each class, and the number of their descendants.
+    classes: HashMap<usize, (usize, usize)>,
+}
+
+/// Interns the descriptions of nodes, see [`Instances`].
+struct Interner {
+    ids: HashMap<Vec<u8>, usize>,
+    instances: Instances,
+    max_depth: usize,
+}
+
+impl Instances {
+    /// Find the groups with the same content among the descendants of `root`, which are nested
+    /// at most `max_depth` deep.
+    pub(crate) fn new(root: &usvg::Group, max_depth: usize) -> Self {
+        let mut interner = Interner {
+            ids: HashMap::new(),
+            instances: Self {
+                groups: HashMap::new(),
+                classes: HashMap::new(),
+            },
+            max_depth,
+        };
+        interner.group(root, 0);
+        interner.instances
+    }
+
+    /// The class of `group` if other groups have the same content, and the number of its
+    /// descendants.
+    pub(crate) fn get(&self, group: &usvg::Group) -> Option<(usize, usize)> {
+        let class = *self.groups.get(&address(group))?;
+        let (count, descendants) = *self.classes.get(&class)?;
+        (count > 1).then_some((class, descendants))
+    }
+}
+
+impl Interner {
+    /// Returns the class of `node` and the number of its descendants.
+    fn node(&mut self, node: &usvg::Node, depth: usize) -> (usize, usize) {
+        match node {
+            usvg::Node::Group(g) => self.group(g, depth + 1),
+            usvg::Node::Path(path) => {
+                let mut key = vec![1];
+                describe_path(path, &mut key);
+                (self.intern(key), 0)
+            }
+            // These are rare inside of repeated content, so they are never the same.
+            usvg::Node::Image(_) | usvg::Node::Text(_) => {
+                let mut key = vec![2];
+                key.extend(address(node).to_le_bytes());
+                (self.intern(key), 0)
+            }
+        }
+    }
+
+    fn group(&mut self, g: &usvg::Group, depth: usize) -> (usize, usize) {
+        let mut key = vec![0];
+        if depth > self.max_depth {
+            // Too deep to be drawn, so don't look any deeper either.
+            key.extend(address(g).to_le_bytes());
+            return (self.intern(key), 0);
+        }
+        push_f32(&mut key, g.opacity().get());
+        key.push(g.blend_mode() as u8);
+        key.push(g.isolate().into());
+        // Clip paths, masks, filters and paint servers are shared by the nodes using them.
+        key.extend(g.clip_path().map_or(0, address).to_le_bytes());
+        key.extend(g.mask().map_or(0, address).to_le_bytes());
+        for filter in g.filters() {
+            key.extend(address(filter.as_ref()).to_le_bytes());
+        }
+        let bounds = g.layer_bounding_box();
+        for value in [bounds.x(), bounds.y(), bounds.width(), bounds.height()] {
+            push_f32(&mut key, value);
+        }
+        let mut descendants = 0;
+        for child in g.children() {
+            let (class, child_descendants) = self.node(child, depth);
+            descendants += 1 + child_descendants;
+            key.extend(class.to_le_bytes());
+            if let usvg::Node::Group(child) = child {
+                push_transform(&mut key, child.transform());
+            }
+        }
+        let class = self.intern(key);
+        self.instances
+            .classes
+            .entry(class)
+            .or_insert((0, descendants))
+            .0 += 1;
+        self.instances.groups.insert(address(g), class);
+        (class, descendants)
+    }
+
+    fn intern(&mut self, key: Vec<u8>) -> usize {
+        let next = self.ids.len();
+        *self.ids.entry(key).or_insert(next)
+    }
+}
+
+fn describe_path(path: &usvg::Path, key: &mut Vec<u8>) {
+    key.push(path.is_visible().into());
+    key.push(path.paint_order() as u8);
+    key.push(path.rendering_mode() as u8);
+    match path.fill() {
+        Some(fill) => {
+            key.push(1);
+            describe_paint(fill.paint(), key);
+            push_f32(key, fill.opacity().get());
+            key.push(fill.rule() as u8);
+        }
+        None => key.push(0),
+    }
+    match path.stroke() {
+        Some(stroke) => {
+            key.push(1);
+            describe_paint(stroke.paint(), key);
+            push_f32(key, stroke.opacity().get());
+            push_f32(key, stroke.width().get());
+            key.push(stroke.linecap() as u8);
+            key.push(stroke.linejoin() as u8);
+            push_f32(key, stroke.miterlimit().get());
+            let dashes = stroke.dasharray().unwrap_or_default();
+            key.extend(dashes.len().to_le_bytes());
+            for &dash in dashes {
+                push_f32(key, dash);
+            }
+            push_f32(key, stroke.dashoffset());
+        }
+        None => key.push(0),
+    }
+    let data = path.data();
+    key.extend(data.verbs().len().to_le_bytes());
+    key.extend(data.verbs().iter().map(|&verb| verb as u8));
+    for point in data.points() {
+        push_f32(key, point.x);
+        push_f32(key, point.y);
+    }
+}
+
+fn describe_paint(paint: &usvg::Paint, key: &mut Vec<u8>) {
+    match paint {
+        usvg::Paint::Color(color) => key.extend([0, color.red, color.green, color.blue]),
+        usvg::Paint::LinearGradient(gradient) => {
+            key.push(1);
+            key.extend(address(gradient.as_ref()).to_le_bytes());
+        }
+        usvg::Paint::RadialGradient(gradient) => {
+            key.push(2);
+            key.extend(address(gradient.as_ref()).to_le_bytes());
+        }
+        usvg::Paint::Pattern(pattern) => {
+            key.push(3);
+            key.extend(address(pattern.as_ref()).to_le_bytes());
+        }
+    }
+}
+
+fn push_f32(key: &mut Vec<u8>, value: f32) {
+    key.extend(value.to_bits().to_le_bytes());
+}
+
+fn push_transform(key: &mut Vec<u8>, transform: usvg::Transform) {
+    let usvg::Transform {
+        sx,
+        kx,
+        ky,
+        sy,
+        tx,
+        ty,
+    } = transform;
+    for value in [sx, kx, ky, sy, tx, ty] {
+        push_f32(key, value);
+    }
+}
+
+fn address<T>(value: &T) -> usize {
+    std::ptr::from_ref(value) as usize
+}
@@ -42,10 +42,62 @@
 )]
 #![cfg_attr(test, allow(unused_crate_dependencies, reason = "Deferred"))] // Some dev dependencies are only used in tests
 
+mod debug;
+pub use debug::DebugOverlay;
+
+mod current_color;
+pub use current_color::CurrentColorSvg;
+
+mod paint;
+pub use paint::{InvertLightness, MapPalette, PaintOverride, ReplaceColors};
+
+mod incremental;
+mod instancing;
+pub use incremental::{Budget, IncrementalRender};
+
+mod options;
+pub use options::{LevelOfDetail, Limits, RenderOptions};
+
 mod render;
 
+#[cfg(feature = "resvg")]
+mod raster_cache;
+#[cfg(feature = "resvg")]
+pub use raster_cache::RasterCache;
+
 mod error;
-pub use error::Error;
+pub use error::{Error, Limit};
+
+mod hit_test;
+pub use hit_test::{HitPart, NodeRef, hit_test};
+
+mod handler;
+pub use handler::{
+    DefaultErrorHandler, ErrorHandler, StrictErrorHandler, Unsupported, UnsupportedNode,
+};
+
+mod retained;
+pub use retained::RetainedSvg;
+
+mod rendered;
+pub use rendered::RenderedSvg;
+
+mod report;
+pub use report::{FeatureReport, RenderReport};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod task;
+#[cfg(not(target_arch = "wasm32"))]
+pub use task::{RenderTask, render_async};
+
+mod sprite;
+pub use sprite::{Sprite, SpriteSheet};
+
+mod stats;
+pub use stats::RenderStats;
+
+mod svg_scene;
+pub use svg_scene::SvgScene;
 
 pub mod util;
 
@@ -54,17 +106,17 @@ pub use vello;
 
 /// Re-export usvg.
 pub use usvg;
-use vello::kurbo::Affine;
+use vello::kurbo::{Affine, Rect, Shape};
+use vello::peniko::{BlendMode, Fill};
 
 /// Render a [`Scene`](vello::Scene) from an SVG string, with default error handling.
 ///
 /// This will draw a red box over (some) unsupported elements.
-pub fn render(svg: &str) -> Result<vello::Scene, Error> {
+pub fn render(svg: &str) -> Result<RenderedSvg, Error> {
     let opt = usvg::Options::default();
-    let tree = usvg::Tree::from_str(svg, &opt)?;
-    let mut scene = vello::Scene::new();
-    append_tree(&mut scene, &tree);
-    Ok(scene)
+    let parsed = parse(svg, &opt)?;
+    let (rendered, result) = render_parsed(parsed, &mut DefaultErrorHandler);
+    result.map(|()| rendered)
 }
 
 /// Append an SVG to a vello [`Scene`](vello::Scene), with default error handling.
@@ -73,8 +125,58 @@ pub fn render(svg: &str) -> Result<vello::Scene, Error> {
 pub fn append(scene: &mut vello::Scene, svg: &str) -> Result<(), Error> {
     let opt = usvg::Options::default();
     let tree = usvg::Tree::from_str(svg, &opt)?;
-    append_tree(scene, &tree);
-    Ok(())
+    append_tree_with_handler(scene, &tree, &mut DefaultErrorHandler)
+}
+
+/// Render a [`Scene`](vello::Scene) from an SVG string, failing on unsupported elements.
+///
+/// See [`append_tree_strict`] for details.
+pub fn render_strict(svg: &str) -> Result<RenderedSvg, Error> {
+    let opt = usvg::Options::default();
+    let parsed = parse(svg, &opt)?;
+    let (rendered, result) = render_parsed(parsed, &mut StrictErrorHandler);
+    result.map(|()| rendered)
+}
+
+/// Render a [`Scene`](vello::Scene) from SVG data, with default error handling.
+///
+/// Unlike [`render`], this also accepts gzip-compressed (`.svgz`) data.
+///
+/// This will draw a red box over (some) unsupported elements.
+pub fn render_bytes(svg: &[u8]) -> Result<RenderedSvg, Error> {
+    let opt = usvg::Options::default();
+    let parsed = parse_bytes(svg, &opt)?;
+    let (rendered, result) = render_parsed(parsed, &mut DefaultErrorHandler);
+    result.map(|()| rendered)
+}
+
+/// Render a [`Scene`](vello::Scene) from an SVG or SVGZ file, with default error handling.
+///
+/// Relative paths in the file, such as those of `<image>` elements, are resolved relative to
+/// the directory containing it.
+///
+/// This will draw a red box over (some) unsupported elements.
+pub fn render_file(path: impl AsRef<std::path::Path>) -> Result<RenderedSvg, Error> {
+    let path = path.as_ref();
+    let data = std::fs::read(path)?;
+    let opt = usvg::Options {
+        resources_dir: path.parent().map(std::path::Path::to_path_buf),
+        ..Default::default()
+    };
+    let parsed = parse_bytes(&data, &opt)?;
+    let (rendered, result) = render_parsed(parsed, &mut DefaultErrorHandler);
+    result.map(|()| rendered)
+}
+
+/// Append SVG data to a vello [`Scene`](vello::Scene), with default error handling.
+///
+/// Unlike [`append`], this also accepts gzip-compressed (`.svgz`) data.
+///
+/// This will draw a red box over (some) unsupported elements.
+pub fn append_bytes(scene: &mut vello::Scene, svg: &[u8]) -> Result<(), Error> {
+    let opt = usvg::Options::default();
+    let parsed = parse_bytes(svg, &opt)?;
+    append_tree_with_handler(scene, &parsed.tree, &mut DefaultErrorHandler)
 }
 
 /// Append an SVG to a vello [`Scene`](vello::Scene), with user-provided error handling logic.
@@ -87,24 +189,148 @@ pub fn append_with<F: FnMut(&mut vello::Scene, &usvg::Node)>(
 ) -> Result<(), Error> {
     let opt = usvg::Options::default();
     let tree = usvg::Tree::from_str(svg, &opt)?;
-    append_tree_with(scene, &tree, error_handler);
-    Ok(())
+    append_tree_with_handler(scene, &tree, error_handler)
 }
 
 /// Render a [`Scene`](vello::Scene) from a [`usvg::Tree`], with default error handling.
 ///
+/// A tree doesn't retain the `viewBox` of its root element, so [`RenderedSvg::view_box`] is
+/// always `None`; use [`render`] or [`render_bytes`] if it is needed.
+///
 /// This will draw a red box over (some) unsupported elements.
-pub fn render_tree(svg: &usvg::Tree) -> vello::Scene {
+pub fn render_tree(svg: &usvg::Tree) -> RenderedSvg {
+    // The default error handler never fails, and nothing is limited by default.
+    let (rendered, _) = render_tree_inner(
+        svg,
+        None,
+        &RenderOptions::default(),
+        &mut DefaultErrorHandler,
+    );
+    rendered
+}
+
+/// Render a [`Scene`](vello::Scene) from a [`usvg::Tree`], with an [`ErrorHandler`].
+///
+/// Returns the first error returned by the handler, or an error if the document exceeds a limit.
+pub fn render_tree_with_handler<H: ErrorHandler + ?Sized>(
+    svg: &usvg::Tree,
+    error_handler: &mut H,
+) -> Result<RenderedSvg, Error> {
+    render_tree_with_options(svg, &RenderOptions::default(), error_handler)
+}
+
+/// Render a [`Scene`](vello::Scene) from a [`usvg::Tree`], with [`RenderOptions`] and an
+/// [`ErrorHandler`].
+///
+/// Returns the first error returned by the handler, or an error if the document exceeds a limit.
+pub fn render_tree_with_options<H: ErrorHandler + ?Sized>(
+    svg: &usvg::Tree,
+    options: &RenderOptions,
+    error_handler: &mut H,
+) -> Result<RenderedSvg, Error> {
+    let (rendered, result) = render_tree_inner(svg, None, options, error_handler);
+    result.map(|()| rendered)
+}
+
+/// Render a [`Scene`](vello::Scene) from a [`usvg::Tree`], with [`RenderOptions`] and default
+/// error handling, encoding independent parts of the document on multiple threads.
+///
+/// The children of the first group with more than one child, which is usually the root group or
+/// the group for its view box, are encoded in parallel with [rayon]. The scene draws the same as
+/// the one of [`render_tree_with_options`], though each part starts by encoding its transform and
+/// style again, and the report and statistics are identical, except for the timings.
+///
+/// If the document exceeds a limit, or uses [`RenderOptions::debug`],
+/// [`RenderOptions::instancing`] or [`RenderOptions::merge_paths`], it is rendered on the
+/// current thread instead.
+///
+/// Returns an error if the document exceeds a limit.
+#[cfg(feature = "rayon")]
+pub fn render_tree_parallel(
+    svg: &usvg::Tree,
+    options: &RenderOptions,
+) -> Result<RenderedSvg, Error> {
     let mut scene = vello::Scene::new();
-    append_tree(&mut scene, svg);
-    scene
+    let mut cx = render::Context::new(&scene, options, DefaultErrorHandler);
+    // The debug overlay is drawn on top of everything, groups are only encoded once when
+    // instancing, and runs of paths are merged, so these need all nodes in one traversal.
+    cx.parallel = options.debug.is_none() && !options.instancing && !options.merge_paths;
+    let transform = options.origin_transform();
+    if render::render_group(&mut scene, svg.root(), transform, &mut cx).is_err() {
+        // The traversal might have stopped at a different node than a serial one.
+        return render_tree_with_options(svg, options, &mut DefaultErrorHandler);
+    }
+    let (report, stats) = cx.finish(&mut scene);
+    let mut rendered = RenderedSvg::new(scene, svg, None, report, stats);
+    rendered.bounds = transform.transform_rect_bbox(rendered.bounds);
+    Ok(rendered)
+}
+
+/// Render a single node of a [`usvg::Tree`] and its descendants, e.g. one artboard or icon of a
+/// larger document.
+///
+/// The transforms, opacity, blend modes and clip paths of the groups containing the node are
+/// applied, but nothing else in the document is drawn. Nodes inside nested SVG images and text
+/// can't be rendered on their own.
+///
+/// If `re_origin` is true, the node is moved so that the top left corner of its bounding box
+/// (including strokes) is at the origin, and [`RenderedSvg::size`] is the size of that
+/// bounding box. Otherwise the node is drawn where it is in the document, which has its usual
+/// size. [`RenderedSvg::bounds`] is the bounding box of the node in either case, moved by
+/// [`RenderOptions::origin`] like the scene.
+///
+/// Returns [`Error::NodeNotFound`] if no node has the given `id`.
+pub fn render_node_by_id<H: ErrorHandler + ?Sized>(
+    svg: &usvg::Tree,
+    id: &str,
+    re_origin: bool,
+    options: &RenderOptions,
+    error_handler: &mut H,
+) -> Result<RenderedSvg, Error> {
+    let path = render::find_path(svg.root(), id).unwrap_or_default();
+    let Some(&node) = path.last() else {
+        return Err(Error::NodeNotFound { id: id.to_owned() });
+    };
+    let mut bounds = node.abs_layer_bounding_box().map_or_else(
+        || util::to_rect(&node.abs_stroke_bounding_box()),
+        |rect| util::to_rect(&rect.to_rect()),
+    );
+    let transform = if re_origin {
+        let offset = bounds.origin().to_vec2();
+        bounds = bounds - offset;
+        Affine::translate(-offset)
+    } else {
+        Affine::IDENTITY
+    };
+
+    let mut scene = vello::Scene::new();
+    let mut cx = render::Context::new(&scene, options, render::Borrowed(error_handler));
+    let transform = options.origin_transform() * transform;
+    let result = render::render_path(&mut scene, svg.root(), &path, transform, &mut cx);
+    let (report, stats) = cx.finish(&mut scene);
+    let mut rendered = RenderedSvg::new(scene, svg, None, report, stats);
+    if re_origin {
+        rendered.size = bounds.size();
+    }
+    rendered.bounds = options.origin_transform().transform_rect_bbox(bounds);
+    result.map(|()| rendered)
+}
+
+/// List the features of a [`usvg::Tree`] which can't be rendered exactly.
+///
+/// This is the same report as in [`RenderedSvg::report`], found by walking the tree without
+/// encoding it. Raster images are reported as undecodable if their header can't be read; images
+/// which are only corrupted further in are found by rendering them.
+pub fn analyze(svg: &usvg::Tree) -> RenderReport {
+    RenderReport::analyze(svg.root())
 }
 
 /// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene), with default error handling.
 ///
 /// This will draw a red box over (some) unsupported elements.
 pub fn append_tree(scene: &mut vello::Scene, svg: &usvg::Tree) {
-    append_tree_with(scene, svg, &mut util::default_error_handler);
+    // The default error handler never fails, and nothing is limited by default.
+    append_tree_with_handler(scene, svg, &mut DefaultErrorHandler).ok();
 }
 
 /// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene), with user-provided error handling logic.
@@ -115,13 +341,212 @@ pub fn append_tree_with<F: FnMut(&mut vello::Scene, &usvg::Node)>(
     svg: &usvg::Tree,
     error_handler: &mut F,
 ) {
-    render::render_group(scene, svg.root(), Affine::IDENTITY, error_handler);
+    // Closures never fail, and nothing is limited by default.
+    append_tree_with_handler(scene, svg, error_handler).ok();
+}
+
+/// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene), with an [`ErrorHandler`] which
+/// is told why each unsupported node can't be drawn.
+///
+/// Returns the first error returned by the handler, or an error if the document exceeds a limit.
+/// The scene may contain partial output in that case.
+pub fn append_tree_with_handler<H: ErrorHandler + ?Sized>(
+    scene: &mut vello::Scene,
+    svg: &usvg::Tree,
+    error_handler: &mut H,
+) -> Result<(), Error> {
+    append_tree_with_options(scene, svg, &RenderOptions::default(), error_handler)
+}
+
+/// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene), with [`RenderOptions`] and an
+/// [`ErrorHandler`].
+///
+/// Returns the first error returned by the handler, or an error if the document exceeds a limit.
+/// The scene may contain partial output in that case.
+pub fn append_tree_with_options<H: ErrorHandler + ?Sized>(
+    scene: &mut vello::Scene,
+    svg: &usvg::Tree,
+    options: &RenderOptions,
+    error_handler: &mut H,
+) -> Result<(), Error> {
+    let mut cx = render::Context::new(scene, options, render::Borrowed(error_handler));
+    let transform = options.origin_transform();
+    let result = render::render_group(scene, svg.root(), transform, &mut cx);
+    cx.finish(scene);
+    result
+}
+
+/// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene), failing on unsupported elements.
+///
+/// Instead of drawing red boxes, this returns an error for the first element which can't be drawn
+/// (such as an undecodable image or a pattern fill), or which uses a feature that would be
+/// ignored (such as masks and filters). The scene may contain partial output in that case.
+pub fn append_tree_strict(scene: &mut vello::Scene, svg: &usvg::Tree) -> Result<(), Error> {
+    append_tree_with_handler(scene, svg, &mut StrictErrorHandler)
+}
+
+/// Append an [`usvg::Tree`] to a vello [`Scene`](vello::Scene) inside a single layer, with
+/// user-provided error handling logic.
+///
+/// The whole SVG is drawn with `transform` applied, and composited onto the scene using the given
+/// `alpha` and `blend_mode`. If `clip` is given, the SVG is clipped to that shape, which is
+/// interpreted in the same coordinate space as the SVG (i.e. it is also affected by `transform`).
+/// Otherwise the layer is bounded by the content of the SVG.
+///
+/// When passing no clip shape, the shape type still needs to be named, e.g. `None::<&Rect>`.
+///
+/// Returns the first error returned by the handler, or an error if the document exceeds a limit.
+/// The layer is popped in either case.
+///
+/// See the [module level documentation](crate#unsupported-features) for a list of some unsupported svg features
+pub fn append_tree_composited<H: ErrorHandler + ?Sized>(
+    scene: &mut vello::Scene,
+    svg: &usvg::Tree,
+    transform: Affine,
+    alpha: f32,
+    blend_mode: impl Into<BlendMode>,
+    clip: Option<&impl Shape>,
+    error_handler: &mut H,
+) -> Result<(), Error> {
+    match clip {
+        Some(clip) => scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, clip),
+        None => {
+            let rect = util::to_rect(&svg.root().layer_bounding_box().to_rect());
+            scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &rect);
+        }
+    }
+    let mut cx = render::Context::new(
+        scene,
+        &RenderOptions::default(),
+        render::Borrowed(error_handler),
+    );
+    let result = render::render_group(scene, svg.root(), transform, &mut cx);
+    scene.pop_layer();
+    cx.finish(scene);
+    result
+}
+
+/// Render a tree to a new scene, also returning the partial output if rendering fails.
+fn render_tree_inner<H: ErrorHandler + ?Sized>(
+    svg: &usvg::Tree,
+    view_box: Option<Rect>,
+    options: &RenderOptions,
+    error_handler: &mut H,
+) -> (RenderedSvg, Result<(), Error>) {
+    let mut scene = vello::Scene::new();
+    let mut cx = render::Context::new(&scene, options, render::Borrowed(error_handler));
+    let transform = options.origin_transform();
+    let result = render::render_group(&mut scene, svg.root(), transform, &mut cx);
+    let (report, stats) = cx.finish(&mut scene);
+    let mut rendered = RenderedSvg::new(scene, svg, view_box, report, stats);
+    rendered.bounds = transform.transform_rect_bbox(rendered.bounds);
+    (rendered, result)
+}
+
+/// Render a document parsed by this crate, with default options.
+fn render_parsed<H: ErrorHandler + ?Sized>(
+    parsed: Parsed,
+    error_handler: &mut H,
+) -> (RenderedSvg, Result<(), Error>) {
+    let (mut rendered, result) = render_tree_inner(
+        &parsed.tree,
+        parsed.view_box,
+        &RenderOptions::default(),
+        error_handler,
+    );
+    rendered.stats.parse_time = parsed.parse_time;
+    (rendered, result)
+}
+
+/// A parsed document, along with what [`usvg::Tree`] doesn't retain.
+struct Parsed {
+    tree: usvg::Tree,
+    /// The `viewBox` of the root element.
+    view_box: Option<Rect>,
+    parse_time: Option<std::time::Duration>,
+}
+
+/// Parse an SVG string.
+fn parse(svg: &str, opt: &usvg::Options<'_>) -> Result<Parsed, Error> {
+    let timer = stats::Timer::start();
+    // Matches the options used by `usvg::Tree::from_str`.
+    let xml_opt = usvg::roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = usvg::roxmltree::Document::parse_with_options(svg, xml_opt)
+        .map_err(usvg::Error::ParsingFailed)?;
+    let view_box = doc
+        .root_element()
+        .attribute("viewBox")
+        .and_then(parse_view_box);
+    let tree = usvg::Tree::from_xmltree(&doc, opt)?;
+    Ok(Parsed {
+        tree,
+        view_box,
+        parse_time: timer.elapsed(),
+    })
+}
+
+/// Parse SVG data, which may be gzip-compressed, in the same way as [`parse`].
+fn parse_bytes(data: &[u8], opt: &usvg::Options<'_>) -> Result<Parsed, Error> {
+    let timer = stats::Timer::start();
+    let decompressed;
+    // Matches the detection used by `usvg::Tree::from_data`.
+    let data = if data.starts_with(&[0x1f, 0x8b]) {
+        decompressed = usvg::decompress_svgz(data)?;
+        &decompressed
+    } else {
+        data
+    };
+    let text = std::str::from_utf8(data).map_err(|_| usvg::Error::NotAnUtf8Str)?;
+    let parsed = parse(text, opt)?;
+    Ok(Parsed {
+        // Include the time taken to decompress the data.
+        parse_time: timer.elapsed(),
+        ..parsed
+    })
+}
+
+fn parse_view_box(value: &str) -> Option<Rect> {
+    let mut numbers = value
+        .split(|c: char| c.is_ascii_whitespace() || c == ',')
+        .filter(|number| !number.is_empty())
+        .map(str::parse::<f64>);
+    let (Some(Ok(x)), Some(Ok(y)), Some(Ok(width)), Some(Ok(height)), None) = (
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+    ) else {
+        return None;
+    };
+    (width > 0.0 && height > 0.0).then(|| Rect::new(x, y, x + width, y + height))
 }
 
 #[cfg(test)]
 mod tests {
-    // CI will fail unless cargo nextest can execute at least one test per workspace.
-    // Delete this dummy test once we have an actual real test.
+    use super::parse_view_box;
+    use vello::kurbo::Rect;
+
+    #[test]
+    fn view_box_separators() {
+        let expected = Some(Rect::new(-1.0, 2.0, 9.0, 22.0));
+        assert_eq!(parse_view_box("-1 2 10 20"), expected, "space separated");
+        assert_eq!(parse_view_box("-1,2,10,20"), expected, "comma separated");
+        assert_eq!(
+            parse_view_box(" -1, 2\t10 ,20 "),
+            expected,
+            "mixed separators"
+        );
+    }
+
     #[test]
-    fn dummy_test_until_we_have_a_real_test() {}
+    fn invalid_view_box() {
+        assert_eq!(parse_view_box("0 0 10"), None, "too few numbers");
+        assert_eq!(parse_view_box("0 0 10 10 10"), None, "too many numbers");
+        assert_eq!(parse_view_box("0 0 0 10"), None, "zero width");
+        assert_eq!(parse_view_box("0 0 ten 10"), None, "not a number");
+    }
 }
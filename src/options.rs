@@ -0,0 +1,161 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{DebugOverlay, PaintOverride};
+use std::sync::Arc;
+use vello::kurbo::{Affine, Point, Rect};
+
+/// Options for rendering a document, see [`render_tree_with_options`](crate::render_tree_with_options).
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Limits on the resources used while rendering.
+    pub limits: Limits,
+    /// Draw the document moved by the negation of this point, and encode the geometry relative to
+    /// it, to keep the precision of documents with large coordinates, e.g. GIS and CAD exports.
+    ///
+    /// Vello encodes coordinates and transforms as 32-bit floats, so zooming far into content
+    /// that is far from the origin shows jittery, misaligned edges. Setting this to the center of
+    /// the region being viewed keeps the coordinates encoded for it small; the scene should then
+    /// be appended with a translation by this point, composed into the transform it's drawn with
+    /// in double precision. Precision already lost when usvg parsed the document can't be
+    /// recovered.
+    ///
+    /// This is in the coordinate space the scene would have without it, and the
+    /// [`viewport`](Self::viewport) and [`RenderedSvg::bounds`](crate::RenderedSvg::bounds) are
+    /// relative to it.
+    pub origin: Option<Point>,
+    /// Only encode the nodes which are at least partly inside of this rectangle, in the coordinate
+    /// space of the scene being encoded.
+    ///
+    /// Nodes are culled by their bounds including strokes and filter regions, so this never
+    /// changes what is drawn inside of the rectangle. When zoomed into a large document, this
+    /// makes encoding scale with the visible part of it.
+    ///
+    /// This has no effect on a [`RetainedSvg`](crate::RetainedSvg), whose nodes can be moved
+    /// outside of the bounds of their ancestors.
+    pub viewport: Option<Rect>,
+    /// Skip the nodes which are too small to be seen at the scale the scene is drawn at.
+    ///
+    /// This has no effect on a [`RetainedSvg`](crate::RetainedSvg), whose nodes can be scaled
+    /// beyond the bounds of their ancestors.
+    pub level_of_detail: Option<LevelOfDetail>,
+    /// Encode groups with exactly the same content once, and append that encoding for each of
+    /// them with their own transform, e.g. for the copies of a symbol placed by `<use>` elements.
+    ///
+    /// usvg expands each `<use>` element into a copy of the content it references, so this can
+    /// make encoding documents with many copies of the same symbols, such as markers on a map,
+    /// much faster. The scene still contains each copy. Finding the groups with the same content
+    /// takes time proportional to the size of the document.
+    ///
+    /// The [report](crate::RenderedSvg::report) and [statistics](crate::RenderedSvg::stats) only
+    /// count the content of such groups once, see [`RenderStats::instances`](crate::RenderStats::instances).
+    /// This has no effect with a [`debug`](Self::debug) overlay.
+    pub instancing: bool,
+    /// Draw runs of sibling paths which are only filled, with the same opaque color and fill
+    /// rule, as one fill, e.g. for the thousands of shapes exported by plotting tools.
+    ///
+    /// Paths are only merged if their bounding boxes are at least one unit apart in the
+    /// coordinate space of the scene, so that no pixel is covered by more than one of them and
+    /// the output is identical, as long as the scene is drawn with a transform which maps whole
+    /// units to whole pixels, e.g. an integer scale and translation. Paths which are scaled up
+    /// when drawing are merged less often than they could be.
+    ///
+    /// The [statistics](crate::RenderedSvg::stats) count a merged fill once, see
+    /// [`RenderStats::merged_paths`](crate::RenderStats::merged_paths). This has no effect with
+    /// a [`debug`](Self::debug) overlay.
+    pub merge_paths: bool,
+    /// Rewrite the colors of the document, e.g. for theming.
+    pub paint_override: Option<Arc<dyn PaintOverride>>,
+    /// Draw a debug overlay on top of the document.
+    pub debug: Option<DebugOverlay>,
+}
+
+impl RenderOptions {
+    /// The transform which moves the document to [`origin`](Self::origin).
+    pub(crate) fn origin_transform(&self) -> Affine {
+        self.origin.map_or(Affine::IDENTITY, |origin| {
+            Affine::translate(-origin.to_vec2())
+        })
+    }
+}
+
+/// Skipping details which are too small to be seen, e.g. when drawing a thumbnail, see
+/// [`RenderOptions::level_of_detail`].
+///
+/// A node is skipped, together with its descendants, when its bounds including strokes and filter
+/// regions fit inside of a square of [`min_size`](Self::min_size) device pixels. Many such nodes
+/// together, such as fine hatching, can still cover a visible area, so this trades accuracy for
+/// encoding time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelOfDetail {
+    /// The scale from the coordinate space of the scene being encoded to device pixels, e.g. the
+    /// scale of the transform the scene is drawn with, times the scale factor of the display.
+    pub scale: f64,
+    /// The size in device pixels below which nodes are skipped.
+    pub min_size: f64,
+}
+
+impl LevelOfDetail {
+    /// Skip nodes smaller than half a device pixel at `scale`.
+    pub fn new(scale: f64) -> Self {
+        Self {
+            scale,
+            min_size: 0.5,
+        }
+    }
+}
+
+/// Limits on the resources used while rendering a document, to render untrusted documents safely.
+///
+/// When a limit is exceeded, rendering stops with [`Error::LimitExceeded`](crate::Error::LimitExceeded),
+/// and the content drawn so far is kept. Nothing is limited by default; use
+/// [`Limits::UNTRUSTED`] for documents which might have been crafted to use excessive resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of nested groups, nested SVG images and text nodes.
+    pub max_nesting_depth: usize,
+    /// The maximum number of nested SVG images.
+    pub max_nested_svg_depth: usize,
+    /// The maximum number of nodes visited, including those inside nested SVG images and text.
+    pub max_nodes: usize,
+    /// The maximum number of path segments drawn, counting each fill and stroke separately.
+    pub max_path_segments: usize,
+    /// The maximum number of pixels in a single raster image.
+    ///
+    /// This is checked before the image is decoded.
+    pub max_image_pixels: u64,
+    /// The maximum total size of the decoded raster images, in bytes.
+    pub max_decoded_bytes: usize,
+}
+
+impl Limits {
+    /// No limits, for documents which are trusted. This is the default.
+    ///
+    /// Deeply nested documents can still overflow the stack.
+    pub const UNLIMITED: Self = Self {
+        max_nesting_depth: usize::MAX,
+        max_nested_svg_depth: usize::MAX,
+        max_nodes: usize::MAX,
+        max_path_segments: usize::MAX,
+        max_image_pixels: u64::MAX,
+        max_decoded_bytes: usize::MAX,
+    };
+
+    /// Limits which are generous enough for typical documents, while bounding the time and memory
+    /// a crafted document can use.
+    pub const UNTRUSTED: Self = Self {
+        max_nesting_depth: 256,
+        max_nested_svg_depth: 8,
+        max_nodes: 1_000_000,
+        max_path_segments: 10_000_000,
+        // 16384 by 16384 pixels.
+        max_image_pixels: 1 << 28,
+        max_decoded_bytes: 1 << 30,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use vello::peniko::Color;
+
+/// Rewrites the colors of a document while it is rendered, e.g. for theming or a dark mode.
+///
+/// Set this as [`RenderOptions::paint_override`](crate::RenderOptions::paint_override).
+pub trait PaintOverride: std::fmt::Debug + Send + Sync {
+    /// Map the color of a solid fill or stroke, or of a gradient stop.
+    ///
+    /// The alpha of `color` includes the opacity of the fill or stroke.
+    fn map_color(&self, color: Color) -> Color;
+
+    /// The color to draw raster images in, keeping their shape and transparency.
+    ///
+    /// By default, images are drawn unchanged.
+    fn image_tint(&self) -> Option<Color> {
+        None
+    }
+}
+
+/// Draw every fill, stroke and raster image in the same color, as is usual for monochrome icons.
+///
+/// The transparency of the document is kept, multiplied by the alpha of the color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplaceColors(pub Color);
+
+impl PaintOverride for ReplaceColors {
+    fn map_color(&self, color: Color) -> Color {
+        self.0.multiply_alpha(color.components[3])
+    }
+
+    fn image_tint(&self) -> Option<Color> {
+        Some(self.0)
+    }
+}
+
+/// Invert the perceptual lightness of each color, keeping its hue, e.g. to adapt a document to a
+/// dark mode.
+///
+/// Raster images are drawn unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InvertLightness;
+
+impl PaintOverride for InvertLightness {
+    fn map_color(&self, color: Color) -> Color {
+        color.map_lightness(|lightness| 1.0 - lightness)
+    }
+}
+
+/// Replace each color which exactly matches one in a palette with its counterpart in another.
+///
+/// Colors are compared by their 8-bit RGB values, ignoring alpha, and the alpha of the original
+/// color is kept. Other colors and raster images are drawn unchanged.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MapPalette {
+    /// Pairs of colors to replace, and the colors to replace them with.
+    pub colors: Vec<(Color, Color)>,
+}
+
+impl MapPalette {
+    /// Map each color in `from` to the color at the same position in `to`.
+    pub fn new(from: impl IntoIterator<Item = Color>, to: impl IntoIterator<Item = Color>) -> Self {
+        Self {
+            colors: from.into_iter().zip(to).collect(),
+        }
+    }
+}
+
+impl PaintOverride for MapPalette {
+    fn map_color(&self, color: Color) -> Color {
+        let rgb = |color: Color| {
+            let rgba = color.to_rgba8();
+            [rgba.r, rgba.g, rgba.b]
+        };
+        self.colors
+            .iter()
+            .find(|(from, _)| rgb(*from) == rgb(color))
+            .map_or(color, |(_, to)| to.with_alpha(color.components[3]))
+    }
+}
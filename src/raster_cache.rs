@@ -0,0 +1,214 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use vello::Scene;
+use vello::kurbo::{Affine, Rect, Vec2};
+use vello::peniko::{Blob, Color, ImageAlphaType, ImageBrush, ImageData, ImageFormat};
+
+/// SVG documents rasterized on the CPU with [resvg], at whole pixel sizes, so that many small
+/// icons can be drawn as images instead of encoding their paths every frame.
+///
+/// Images are cached by document, size and color, and the least recently used ones are evicted
+/// once they take more memory than the budget. Documents are identified by their [`Arc`], which
+/// the cache keeps alive until all images of it are evicted.
+///
+/// Unlike the rest of this crate, this draws everything resvg supports, including filters and
+/// masks.
+pub struct RasterCache {
+    entries: HashMap<Key, Entry>,
+    max_bytes: usize,
+    bytes: usize,
+    /// Incremented on each lookup, to find the least recently used images.
+    clock: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    /// The address of the document, which can't be reused while its image is cached.
+    document: usize,
+    width: u32,
+    height: u32,
+    color: Option<[u8; 4]>,
+}
+
+struct Entry {
+    /// Keeps the address in the key from being reused.
+    _document: Arc<usvg::Tree>,
+    image: ImageBrush,
+    last_used: u64,
+}
+
+impl RasterCache {
+    /// Create an empty cache, which keeps images taking up to `max_bytes` of memory.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_bytes,
+            bytes: 0,
+            clock: 0,
+        }
+    }
+
+    /// The image of `svg` stretched to `width` by `height` pixels, rasterizing it if it isn't
+    /// cached.
+    ///
+    /// If `color` is given, every pixel is drawn in that color, keeping its transparency, as is
+    /// usual for monochrome icons.
+    ///
+    /// Returns `None` if either dimension is zero.
+    pub fn get(
+        &mut self,
+        svg: &Arc<usvg::Tree>,
+        width: u32,
+        height: u32,
+        color: Option<Color>,
+    ) -> Option<&ImageBrush> {
+        let key = Key {
+            document: Arc::as_ptr(svg) as usize,
+            width,
+            height,
+            color: color.map(|color| color.to_rgba8().to_u8_array()),
+        };
+        self.clock += 1;
+        if !self.entries.contains_key(&key) {
+            let image = rasterize(svg, width, height, color)?;
+            self.bytes += image.image.data.len();
+            self.entries.insert(
+                key,
+                Entry {
+                    _document: Arc::clone(svg),
+                    image,
+                    last_used: self.clock,
+                },
+            );
+            self.evict();
+        }
+        let entry = self.entries.get_mut(&key)?;
+        entry.last_used = self.clock;
+        Some(&entry.image)
+    }
+
+    /// Draw `svg` into `scene`, scaled uniformly to fit inside of `rect` and centered in it.
+    ///
+    /// The image is rasterized at the size it has in device pixels when the scene is drawn
+    /// with `scale` applied, rounded to whole pixels, so that it looks sharp. See
+    /// [`get`](Self::get) for `color`.
+    pub fn append_to(
+        &mut self,
+        scene: &mut Scene,
+        svg: &Arc<usvg::Tree>,
+        rect: Rect,
+        scale: f64,
+        color: Option<Color>,
+    ) {
+        let size = svg.size();
+        let (width, height) = (f64::from(size.width()), f64::from(size.height()));
+        let fit = (rect.width() / width).min(rect.height() / height) * scale;
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "icons are much smaller than `u32::MAX` pixels, and negative sizes saturate to 0"
+        )]
+        let (pixel_width, pixel_height) =
+            ((width * fit).round() as u32, (height * fit).round() as u32);
+        let Some(image) = self.get(svg, pixel_width, pixel_height, color) else {
+            return;
+        };
+        let offset = rect.center().to_vec2()
+            - Vec2::new(f64::from(pixel_width), f64::from(pixel_height)) / (2.0 * scale);
+        scene.draw_image(
+            image,
+            Affine::translate(offset) * Affine::scale(1.0 / scale),
+        );
+    }
+
+    /// The memory taken by the cached images, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// The number of cached images.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no images are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Evict all images.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    /// Evict the least recently used images until they fit in the budget, except for the most
+    /// recently used one.
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes && self.entries.len() > 1 {
+            let Some((&key, _)) = self.entries.iter().min_by_key(|(_, entry)| entry.last_used)
+            else {
+                return;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.image.image.data.len();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RasterCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RasterCache")
+            .field("images", &self.entries.len())
+            .field("bytes", &self.bytes)
+            .field("max_bytes", &self.max_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rasterize `svg` stretched to `width` by `height` pixels, optionally drawn in `color`.
+fn rasterize(
+    svg: &usvg::Tree,
+    width: u32,
+    height: u32,
+    color: Option<Color>,
+) -> Option<ImageBrush> {
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+    let size = svg.size();
+    let transform = resvg::tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(svg, transform, &mut pixmap.as_mut());
+    let mut data = pixmap.take();
+    if let Some(color) = color {
+        let [r, g, b, a] = color.to_rgba8().to_u8_array();
+        // The pixels are premultiplied, so scale the color by their alpha.
+        let scale = |channel: u8, alpha: u8| {
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "the product of two bytes divided by 255 fits in a byte"
+            )]
+            let scaled = ((u16::from(channel) * u16::from(alpha) + 127) / 255) as u8;
+            scaled
+        };
+        for pixel in data.chunks_exact_mut(4) {
+            let alpha = scale(pixel[3], a);
+            pixel.copy_from_slice(&[scale(r, alpha), scale(g, alpha), scale(b, alpha), alpha]);
+        }
+    }
+    Some(
+        ImageData {
+            data: Blob::new(Arc::new(data)),
+            format: ImageFormat::Rgba8,
+            alpha_type: ImageAlphaType::AlphaPremultiplied,
+            width,
+            height,
+        }
+        .into(),
+    )
+}
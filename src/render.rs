@@ -1,160 +1,1069 @@
 // Copyright 2024 the Vello Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::util;
+use crate::debug::Overlay;
+use crate::error::Limit;
+use crate::instancing::Instances;
+use crate::retained::NodeOverride;
+use crate::stats::Timer;
+use crate::{
+    Error, ErrorHandler, LevelOfDetail, Limits, PaintOverride, RenderOptions, RenderReport,
+    RenderStats, Unsupported, UnsupportedNode, util,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use vello::Scene;
-use vello::kurbo::Affine;
-use vello::peniko::{BlendMode, Fill};
+use vello::kurbo::{Affine, BezPath, Rect, Shape};
+use vello::peniko::{BlendMode, Brush, Fill, Mix};
 
-pub(crate) fn render_group<F: FnMut(&mut Scene, &usvg::Node)>(
+/// The clip shape of layers which shouldn't clip their content.
+const UNBOUNDED: Rect = Rect::new(-1e9, -1e9, 1e9, 1e9);
+
+/// The resources used by a traversal, which are limited by [`Limits`] in total, even when parts of
+/// the document are drawn on other threads.
+#[derive(Default)]
+struct Counters {
+    nodes: AtomicUsize,
+    path_segments: AtomicUsize,
+    #[cfg(feature = "image")]
+    decoded_bytes: AtomicUsize,
+}
+
+impl Counters {
+    /// Add `amount` to `counter`, returning whether the total is still at most `max`.
+    fn add(counter: &AtomicUsize, amount: usize, max: usize) -> bool {
+        counter
+            .fetch_add(amount, Ordering::Relaxed)
+            .saturating_add(amount)
+            <= max
+    }
+}
+
+/// An error handler borrowed by a [`Context`], which owns its error handler.
+pub(crate) struct Borrowed<'a, H: ?Sized>(pub(crate) &'a mut H);
+
+impl<H: ErrorHandler + ?Sized> ErrorHandler for Borrowed<'_, H> {
+    fn on_unsupported(
+        &mut self,
+        scene: &mut Scene,
+        unsupported: &UnsupportedNode<'_>,
+    ) -> Result<(), Error> {
+        self.0.on_unsupported(scene, unsupported)
+    }
+}
+
+/// State shared by the traversal of a whole document.
+pub(crate) struct Context<'a, 't, H> {
+    error_handler: H,
+    /// The groups containing the nodes currently being drawn.
+    ancestors: Vec<&'t usvg::Group>,
+    /// The number of layers containing the nodes currently being drawn.
+    layers: usize,
+    /// The number of nested SVG images containing the nodes currently being drawn.
+    nested_svgs: usize,
+    limits: Limits,
+    /// The resources used so far, shared with the traversals started with `fork`.
+    counters: Arc<Counters>,
+    report: RenderReport,
+    stats: RenderStats,
+    /// When the traversal started, and the number of path segments in the scene at that point.
+    start: (Timer, usize),
+    overlay: Option<Overlay>,
+    /// Changes to the nodes with the given ids, see [`RetainedSvg`](crate::RetainedSvg).
+    ///
+    /// As changed nodes can move outside of the bounds of their ancestors, layers are only
+    /// clipped by clip paths when this is set.
+    pub(crate) overrides: Option<&'a HashMap<String, NodeOverride>>,
+    /// The brush which replaces the fills of the nodes currently being drawn.
+    fill: Option<&'a Brush>,
+    paint_override: Option<Arc<dyn PaintOverride>>,
+    viewport: Option<Rect>,
+    level_of_detail: Option<LevelOfDetail>,
+    /// Whether to encode shapes relative to their centers, see [`RenderOptions::origin`].
+    recenter: bool,
+    /// Whether to encode groups with the same content once, see [`RenderOptions::instancing`].
+    instancing: bool,
+    /// Whether to draw runs of sibling paths as one fill, see [`RenderOptions::merge_paths`].
+    merge_paths: bool,
+    /// The groups with the same content, found when the first group is drawn.
+    instances: Option<Instances>,
+    /// The fragment encoded for each class of [`Instances`].
+    fragments: HashMap<usize, Scene>,
+    /// The groups whose remaining children are drawn by the next calls to [`resume`], if groups
+    /// are drawn incrementally instead of recursively.
+    frames: Option<Vec<Frame<'t>>>,
+    /// Whether to encode the children of the next group with several children in parallel.
+    #[cfg(feature = "rayon")]
+    pub(crate) parallel: bool,
+}
+
+impl<H: ErrorHandler> Context<'_, '_, H> {
+    /// Start a traversal which draws into `scene`.
+    pub(crate) fn new(scene: &Scene, options: &RenderOptions, error_handler: H) -> Self {
+        Self {
+            error_handler,
+            ancestors: Vec::new(),
+            layers: 0,
+            nested_svgs: 0,
+            limits: options.limits,
+            counters: Arc::default(),
+            report: RenderReport::default(),
+            stats: RenderStats::default(),
+            start: (Timer::start(), scene.encoding().n_path_segments as usize),
+            overlay: options.debug.map(Overlay::new),
+            overrides: None,
+            fill: None,
+            paint_override: options.paint_override.clone(),
+            viewport: options.viewport,
+            level_of_detail: options.level_of_detail,
+            recenter: options.origin.is_some(),
+            // The debug overlay draws every node.
+            instancing: options.instancing && options.debug.is_none(),
+            merge_paths: options.merge_paths && options.debug.is_none(),
+            instances: None,
+            fragments: HashMap::new(),
+            frames: None,
+            #[cfg(feature = "rayon")]
+            parallel: false,
+        }
+    }
+
+    /// Draw anything which goes on top of the document, and return the report and statistics.
+    pub(crate) fn finish(mut self, scene: &mut Scene) -> (RenderReport, RenderStats) {
+        let (timer, start_segments) = self.start;
+        self.stats.encode_time = timer.elapsed();
+        self.stats.path_segments = scene.encoding().n_path_segments as usize - start_segments;
+        if let Some(overlay) = self.overlay {
+            overlay.finish(scene);
+        }
+        (self.report, self.stats)
+    }
+
+    /// Move `shape` so that its bounding box is centered on the origin, when re-centering, to
+    /// encode it precisely even if it's far from the origin.
+    ///
+    /// Returns the shape and the transform to draw it with instead of `transform`, as well as the
+    /// transform to apply to brushes first.
+    fn recenter(&self, shape: BezPath, transform: Affine) -> (BezPath, Affine, Affine) {
+        if !self.recenter {
+            return (shape, transform, Affine::IDENTITY);
+        }
+        let offset = shape.bounding_box().center().to_vec2();
+        (
+            Affine::translate(-offset) * shape,
+            transform * Affine::translate(offset),
+            Affine::translate(-offset),
+        )
+    }
+
+    fn to_brush(&self, paint: &usvg::Paint, opacity: usvg::Opacity) -> Option<(Brush, Affine)> {
+        match &self.paint_override {
+            Some(paint_override) => {
+                util::to_brush_with(paint, opacity, |color| paint_override.map_color(color))
+            }
+            None => util::to_brush(paint, opacity),
+        }
+    }
+
+    /// The class of `group` in [`Instances`] and the number of its descendants, if it should be
+    /// drawn by appending a fragment shared with other groups with the same content.
+    fn instance_of(&mut self, node: &usvg::Node, g: &usvg::Group) -> Option<(usize, usize)> {
+        // Changed nodes can differ between instances.
+        if !self.instancing || self.overrides.is_some() {
+            return None;
+        }
+        // The fragment is drawn in the coordinate space of the group.
+        if util::to_affine(&node.abs_transform()).determinant() == 0.0 {
+            return None;
+        }
+        if self.instances.is_none() {
+            let root = self.ancestors.first()?;
+            self.instances = Some(Instances::new(root, self.limits.max_nesting_depth));
+        }
+        self.instances.as_ref()?.get(g)
+    }
+
+    /// Report that `node` exceeds `limit`, returning the error which stops rendering.
+    fn exceeded(&mut self, node_id: &str, limit: Limit) -> Error {
+        self.report.exceeded_limit = Some(limit);
+        Error::LimitExceeded {
+            id: node_id.to_owned(),
+            limit,
+        }
+    }
+
+    /// Account for drawing `count` nodes, starting with `node`.
+    fn add_nodes(&mut self, node: &usvg::Node, count: usize) -> Result<(), Error> {
+        if !Counters::add(&self.counters.nodes, count, self.limits.max_nodes) {
+            return Err(self.exceeded(node.id(), Limit::Nodes));
+        }
+        Ok(())
+    }
+
+    /// Account for drawing a path with `segments` segments.
+    fn add_path_segments(&mut self, node: &usvg::Node, segments: usize) -> Result<(), Error> {
+        let max = self.limits.max_path_segments;
+        if !Counters::add(&self.counters.path_segments, segments, max) {
+            return Err(self.exceeded(node.id(), Limit::PathSegments));
+        }
+        Ok(())
+    }
+
+    /// Decode a raster image, checking its size against the limits first.
+    ///
+    /// Returns `None` if the image can't be decoded.
+    #[cfg(feature = "image")]
+    fn decode_image(
+        &mut self,
+        node: &usvg::Node,
+        kind: &usvg::ImageKind,
+    ) -> Result<Option<image::RgbaImage>, Error> {
+        let Ok((width, height)) = util::raster_image_dimensions(kind) else {
+            return Ok(None);
+        };
+        let pixels = u64::from(width) * u64::from(height);
+        if pixels > self.limits.max_image_pixels {
+            return Err(self.exceeded(node.id(), Limit::ImagePixels));
+        }
+        let bytes = usize::try_from(pixels * 4).unwrap_or(usize::MAX);
+        let decoded_bytes = &self.counters.decoded_bytes;
+        if !Counters::add(decoded_bytes, bytes, self.limits.max_decoded_bytes) {
+            return Err(self.exceeded(node.id(), Limit::DecodedBytes));
+        }
+        let image = util::decode_raw_raster_image(kind).ok();
+        if image.is_none() {
+            decoded_bytes.fetch_sub(bytes, Ordering::Relaxed);
+        }
+        Ok(image)
+    }
+
+    fn enter_layer(&mut self) {
+        self.layers += 1;
+        self.stats.layers += 1;
+        self.stats.max_layer_depth = self.stats.max_layer_depth.max(self.layers);
+    }
+
+    /// Record a fill or stroke drawn with `brush`.
+    fn draw(&mut self, brush: &Brush) {
+        self.stats.paths += 1;
+        if let Brush::Gradient(gradient) = brush {
+            self.stats.gradient_stops += gradient.stops.len();
+        }
+    }
+
+    /// Report a node which can't be drawn exactly.
+    fn unsupported(
+        &mut self,
+        scene: &mut Scene,
+        node: &usvg::Node,
+        reason: Unsupported,
+        transform: Affine,
+    ) -> Result<(), Error> {
+        self.report.record(reason, node.id());
+        if let Some(overlay) = &mut self.overlay {
+            overlay.unsupported(node, transform);
+        }
+        self.error_handler.on_unsupported(
+            scene,
+            &UnsupportedNode {
+                reason,
+                node,
+                ancestors: &self.ancestors,
+                transform,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, 't, H: ErrorHandler> Context<'a, 't, H> {
+    /// Start a traversal of the descendants of the nodes currently being drawn, which draws into
+    /// a separate scene, with default error handling.
+    fn fork(&self, scene: &Scene) -> Context<'a, 't, crate::DefaultErrorHandler> {
+        Context {
+            error_handler: crate::DefaultErrorHandler,
+            ancestors: self.ancestors.clone(),
+            layers: self.layers,
+            nested_svgs: self.nested_svgs,
+            limits: self.limits,
+            counters: self.counters.clone(),
+            report: RenderReport::default(),
+            stats: RenderStats::default(),
+            start: (Timer::start(), scene.encoding().n_path_segments as usize),
+            overlay: None,
+            overrides: self.overrides,
+            fill: self.fill,
+            paint_override: self.paint_override.clone(),
+            viewport: self.viewport,
+            level_of_detail: self.level_of_detail,
+            recenter: self.recenter,
+            instancing: false,
+            merge_paths: false,
+            instances: None,
+            fragments: HashMap::new(),
+            frames: None,
+            parallel: false,
+        }
+    }
+
+    /// Add the report and statistics of a traversal started with [`Self::fork`] to this one,
+    /// after it finished.
+    ///
+    /// The resources it used are already counted, as the counters are shared while drawing.
+    fn join(&mut self, fork: Context<'_, 't, crate::DefaultErrorHandler>) {
+        self.report.merge(fork.report);
+        self.stats.merge(&fork.stats);
+    }
+}
+
+pub(crate) fn render_group<'t, H: ErrorHandler>(
     scene: &mut Scene,
-    group: &usvg::Group,
-    transform: Affine,
-    error_handler: &mut F,
-) {
-    for node in group.children() {
-        let transform = transform * util::to_affine(&node.abs_transform());
-        match node {
-            usvg::Node::Group(g) => {
-                let alpha = g.opacity().get();
-                let blend_mode: BlendMode = match g.blend_mode() {
-                    usvg::BlendMode::Normal => vello::peniko::Mix::Normal.into(),
-                    usvg::BlendMode::Multiply => vello::peniko::Mix::Multiply.into(),
-                    usvg::BlendMode::Screen => vello::peniko::Mix::Screen.into(),
-                    usvg::BlendMode::Overlay => vello::peniko::Mix::Overlay.into(),
-                    usvg::BlendMode::Darken => vello::peniko::Mix::Darken.into(),
-                    usvg::BlendMode::Lighten => vello::peniko::Mix::Lighten.into(),
-                    usvg::BlendMode::ColorDodge => vello::peniko::Mix::ColorDodge.into(),
-                    usvg::BlendMode::ColorBurn => vello::peniko::Mix::ColorBurn.into(),
-                    usvg::BlendMode::HardLight => vello::peniko::Mix::HardLight.into(),
-                    usvg::BlendMode::SoftLight => vello::peniko::Mix::SoftLight.into(),
-                    usvg::BlendMode::Difference => vello::peniko::Mix::Difference.into(),
-                    usvg::BlendMode::Exclusion => vello::peniko::Mix::Exclusion.into(),
-                    usvg::BlendMode::Hue => vello::peniko::Mix::Hue.into(),
-                    usvg::BlendMode::Saturation => vello::peniko::Mix::Saturation.into(),
-                    usvg::BlendMode::Color => vello::peniko::Mix::Color.into(),
-                    usvg::BlendMode::Luminosity => vello::peniko::Mix::Luminosity.into(),
-                };
-
-                let clipped = match g
-                    .clip_path()
-                    // support clip-path with a single path
-                    .and_then(|path| path.root().children().first())
-                {
-                    Some(usvg::Node::Path(clip_path)) => {
-                        let local_path = util::to_bez_path(clip_path);
-                        scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &local_path);
-
-                        true
-                    }
-                    _ => {
-                        // Use bounding box as the clip path.
-                        let bounding_box = g.layer_bounding_box();
-                        let rect = vello::kurbo::Rect::from_origin_size(
-                            (bounding_box.x(), bounding_box.y()),
-                            (bounding_box.width() as f64, bounding_box.height() as f64),
-                        );
-                        scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &rect);
-                        true
-                    }
-                };
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    enter_group(group, cx)?;
+    let result = render_children(scene, group, base_transform, cx);
+    cx.ancestors.pop();
+    result
+}
 
-                render_group(scene, g, Affine::IDENTITY, error_handler);
+/// A group whose children are being drawn incrementally, see [`resume`].
+pub(crate) struct Frame<'t> {
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    /// The index of the next child to draw.
+    next: usize,
+    exit: Exit,
+}
 
-                if clipped {
-                    scene.pop_layer();
-                }
+/// What to undo after drawing the children of a group.
+#[derive(Clone, Copy)]
+enum Exit {
+    Nothing,
+    Layer,
+    NestedSvg,
+}
+
+impl Exit {
+    fn leave<H: ErrorHandler>(self, scene: &mut Scene, cx: &mut Context<'_, '_, H>) {
+        match self {
+            Self::Nothing => {}
+            Self::Layer => pop_group_layer(scene, cx),
+            Self::NestedSvg => cx.nested_svgs -= 1,
+        }
+    }
+}
+
+/// Start drawing `group` incrementally, one node each time `resume` asks for more.
+pub(crate) fn render_group_incremental<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    cx.frames = Some(Vec::new());
+    descend(scene, group, base_transform, Exit::Nothing, cx)
+}
+
+/// Continue drawing the group passed to [`render_group_incremental`], one node at a time, until
+/// `paused` returns true before a node is drawn.
+///
+/// Returns whether the whole group was drawn. After an error, the layers which were pushed are
+/// popped, and nothing more is drawn.
+pub(crate) fn resume<H: ErrorHandler>(
+    scene: &mut Scene,
+    cx: &mut Context<'_, '_, H>,
+    mut paused: impl FnMut() -> bool,
+) -> Result<bool, Error> {
+    loop {
+        let Some(frame) = cx.frames.as_mut().and_then(|frames| frames.last_mut()) else {
+            return Ok(true);
+        };
+        let (group, base_transform) = (frame.group, frame.base_transform);
+        let nodes = group.children().get(frame.next..).unwrap_or_default();
+        if nodes.is_empty() {
+            leave(scene, cx);
+            continue;
+        }
+        if paused() {
+            return Ok(false);
+        }
+        let run = &nodes[..run_length(nodes, base_transform, cx)];
+        if let Some(frame) = cx.frames.as_mut().and_then(|frames| frames.last_mut()) {
+            frame.next += run.len();
+        }
+        if let Err(error) = render_run(scene, run, base_transform, cx) {
+            // Keep the layer stack balanced.
+            while cx.frames.as_ref().is_some_and(|frames| !frames.is_empty()) {
+                leave(scene, cx);
             }
-            usvg::Node::Path(path) => {
-                if !path.is_visible() {
-                    continue;
-                }
-                let local_path = util::to_bez_path(path);
-
-                let do_fill = |scene: &mut Scene, error_handler: &mut F| {
-                    if let Some(fill) = &path.fill() {
-                        if let Some((brush, brush_transform)) =
-                            util::to_brush(fill.paint(), fill.opacity())
-                        {
-                            scene.fill(
-                                match fill.rule() {
-                                    usvg::FillRule::NonZero => Fill::NonZero,
-                                    usvg::FillRule::EvenOdd => Fill::EvenOdd,
-                                },
-                                transform,
-                                &brush,
-                                Some(brush_transform),
-                                &local_path,
-                            );
-                        } else {
-                            error_handler(scene, node);
-                        }
-                    }
-                };
-                let do_stroke = |scene: &mut Scene, error_handler: &mut F| {
-                    if let Some(stroke) = &path.stroke() {
-                        if let Some((brush, brush_transform)) =
-                            util::to_brush(stroke.paint(), stroke.opacity())
-                        {
-                            let conv_stroke = util::to_stroke(stroke);
-                            scene.stroke(
-                                &conv_stroke,
-                                transform,
-                                &brush,
-                                Some(brush_transform),
-                                &local_path,
-                            );
-                        } else {
-                            error_handler(scene, node);
-                        }
-                    }
-                };
-                match path.paint_order() {
-                    usvg::PaintOrder::FillAndStroke => {
-                        do_fill(scene, error_handler);
-                        do_stroke(scene, error_handler);
+            return Err(error);
+        }
+    }
+}
+
+/// Finish drawing the innermost group being drawn incrementally.
+fn leave<H: ErrorHandler>(scene: &mut Scene, cx: &mut Context<'_, '_, H>) {
+    if let Some(frame) = cx.frames.as_mut().and_then(Vec::pop) {
+        cx.ancestors.pop();
+        frame.exit.leave(scene, cx);
+    }
+}
+
+/// Draw the children of `group` and then undo `exit`, or only enter the group if groups are
+/// drawn incrementally, leaving its children to [`resume`].
+fn descend<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    exit: Exit,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    if cx.frames.is_none() {
+        let result = render_group(scene, group, base_transform, cx);
+        // Keep the layer stack balanced, even when bailing out.
+        exit.leave(scene, cx);
+        return result;
+    }
+    if let Err(error) = enter_group(group, cx) {
+        exit.leave(scene, cx);
+        return Err(error);
+    }
+    if let Some(frames) = &mut cx.frames {
+        frames.push(Frame {
+            group,
+            base_transform,
+            next: 0,
+            exit,
+        });
+    }
+    Ok(())
+}
+
+/// Render only the last node of `path`, inside the layers of the groups leading to it.
+///
+/// `path` starts with a child of `group`, and each following node is a child of the one before.
+pub(crate) fn render_path<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    group: &'t usvg::Group,
+    path: &[&'t usvg::Node],
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    enter_group(group, cx)?;
+    let result = match path {
+        [] => Ok(()),
+        [node @ usvg::Node::Group(g), rest @ ..] if !rest.is_empty() => {
+            let transform = base_transform * util::to_affine(&node.abs_transform());
+            push_group_layer(scene, node, g, transform, None, cx).and_then(|()| {
+                let result = render_path(scene, g, rest, base_transform, cx);
+                pop_group_layer(scene, cx);
+                result
+            })
+        }
+        [node, ..] => render_node(scene, node, base_transform, cx),
+    };
+    cx.ancestors.pop();
+    result
+}
+
+/// Find the node with the given `id` among the descendants of `group`, returning the nodes
+/// leading to it, as expected by [`render_path`].
+///
+/// This doesn't search nested SVG images or text.
+pub(crate) fn find_path<'t>(group: &'t usvg::Group, id: &str) -> Option<Vec<&'t usvg::Node>> {
+    fn find<'t>(group: &'t usvg::Group, id: &str, path: &mut Vec<&'t usvg::Node>) -> bool {
+        for node in group.children() {
+            path.push(node);
+            if node.id() == id {
+                return true;
+            }
+            if let usvg::Node::Group(g) = node
+                && find(g, id, path)
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+    let mut path = Vec::new();
+    find(group, id, &mut path).then_some(path)
+}
+
+/// Push `group` onto the ancestors of the nodes being drawn, if that doesn't exceed the
+/// nesting depth limit.
+fn enter_group<'t, H: ErrorHandler>(
+    group: &'t usvg::Group,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    if cx.ancestors.len() >= cx.limits.max_nesting_depth {
+        return Err(cx.exceeded(group.id(), Limit::NestingDepth));
+    }
+    cx.stats.groups += 1;
+    cx.ancestors.push(group);
+    Ok(())
+}
+
+fn render_children<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    if cx.parallel && group.children().len() > 1 {
+        cx.parallel = false;
+        return render_children_parallel(scene, group, base_transform, cx);
+    }
+    let mut nodes = group.children();
+    while !nodes.is_empty() {
+        let (run, rest) = nodes.split_at(run_length(nodes, base_transform, cx));
+        render_run(scene, run, base_transform, cx)?;
+        nodes = rest;
+    }
+    Ok(())
+}
+
+/// The most paths merged into one fill, to bound the time spent checking that they don't
+/// overlap.
+const MAX_MERGED_PATHS: usize = 256;
+
+/// The number of nodes at the start of `nodes` to draw together, which is more than one for a
+/// run of paths drawn as one fill, see [`RenderOptions::merge_paths`].
+///
+/// Paths are only merged if their bounds in the coordinate space of the scene are at least one
+/// unit apart, so that no pixel is covered by more than one of them, and the merged fill draws
+/// exactly the same pixels.
+fn run_length<H: ErrorHandler>(
+    nodes: &[usvg::Node],
+    base_transform: Affine,
+    cx: &Context<'_, '_, H>,
+) -> usize {
+    let Some((first, style)) = nodes.first().and_then(|node| mergeable(node, cx)) else {
+        return 1;
+    };
+    // Half of the distance between the paths on each side.
+    let scene_bounds = |path: &usvg::Path| {
+        base_transform
+            .transform_rect_bbox(util::to_rect(&path.abs_bounding_box()))
+            .inflate(0.5, 0.5)
+    };
+    let mut bounds = vec![scene_bounds(first)];
+    for node in nodes.iter().take(MAX_MERGED_PATHS).skip(1) {
+        let Some((path, next_style)) = mergeable(node, cx) else {
+            break;
+        };
+        let path_bounds = scene_bounds(path);
+        // Overlapping paths can cancel out, depending on their direction and the fill rule, and
+        // the antialiasing of paths which share pixels differs when they are drawn separately.
+        if next_style != style
+            || bounds
+                .iter()
+                .any(|other| !other.intersect(path_bounds).is_zero_area())
+        {
+            break;
+        }
+        bounds.push(path_bounds);
+    }
+    bounds.len()
+}
+
+/// The path drawn by `node`, and its color and fill rule, if it is only filled with an opaque
+/// color, so that it can be drawn in one fill with siblings with the same style which it
+/// doesn't overlap.
+fn mergeable<'n, H: ErrorHandler>(
+    node: &'n usvg::Node,
+    cx: &Context<'_, '_, H>,
+) -> Option<(&'n usvg::Path, ([f32; 4], Fill))> {
+    // Changed nodes aren't drawn in their usual style.
+    if !cx.merge_paths || cx.overrides.is_some() {
+        return None;
+    }
+    let usvg::Node::Path(path) = node else {
+        return None;
+    };
+    if !path.is_visible() || path.stroke().is_some() {
+        return None;
+    }
+    let fill = path.fill()?;
+    let (Brush::Solid(color), _) = cx.to_brush(fill.paint(), fill.opacity())? else {
+        return None;
+    };
+    (color.components[3] == 1.0).then_some((path, (color.components, to_fill(fill.rule()))))
+}
+
+/// Draw `nodes`, which are either a single node or a run of paths found by [`run_length`].
+fn render_run<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    nodes: &'t [usvg::Node],
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    let [first, rest @ ..] = nodes else {
+        return Ok(());
+    };
+    if rest.is_empty() {
+        return render_node(scene, first, base_transform, cx);
+    }
+    let mut merged = BezPath::new();
+    let mut drawn = None;
+    for node in nodes {
+        cx.add_nodes(node, 1)?;
+        if is_skipped(node, base_transform, cx) {
+            continue;
+        }
+        let usvg::Node::Path(path) = node else {
+            continue;
+        };
+        let local_path = util::to_bez_path(path);
+        cx.add_path_segments(node, local_path.elements().len())?;
+        merged.extend(local_path);
+        if drawn.replace(path).is_some() {
+            cx.stats.merged_paths += 1;
+        }
+    }
+    let Some(fill) = drawn.and_then(|path| path.fill()) else {
+        return Ok(());
+    };
+    let Some((brush, brush_transform)) = cx.to_brush(fill.paint(), fill.opacity()) else {
+        return Ok(());
+    };
+    // Siblings which aren't groups have the transform of their parent.
+    let transform = base_transform * util::to_affine(&first.abs_transform());
+    let (merged, transform, brush_offset) = cx.recenter(merged, transform);
+    cx.draw(&brush);
+    scene.fill(
+        to_fill(fill.rule()),
+        transform,
+        &brush,
+        Some(brush_offset * brush_transform),
+        &merged,
+    );
+    Ok(())
+}
+
+fn to_fill(rule: usvg::FillRule) -> Fill {
+    match rule {
+        usvg::FillRule::NonZero => Fill::NonZero,
+        usvg::FillRule::EvenOdd => Fill::EvenOdd,
+    }
+}
+
+/// Render the children of `group` in contiguous chunks, each encoded into a separate scene in
+/// parallel, and appended in order.
+///
+/// Returns an error if rendering them one after the other might have stopped at an error, in
+/// which case the scene is incomplete. The resources used by all chunks are limited together
+/// while they are encoded, but other errors are only found once all chunks are encoded.
+#[cfg(feature = "rayon")]
+fn render_children_parallel<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    group: &'t usvg::Group,
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    use rayon::prelude::*;
+
+    let children = group.children();
+    // A few chunks per thread, to balance the load when the children differ in complexity.
+    let chunk_size = children
+        .len()
+        .div_ceil(rayon::current_num_threads() * 4)
+        .max(1);
+    let chunks = children.len().div_ceil(chunk_size);
+    let mut forks: Vec<_> = (0..chunks)
+        .map(|_| {
+            let fragment = Scene::new();
+            let fork = cx.fork(&fragment);
+            (fragment, fork)
+        })
+        .collect();
+    let results: Vec<Result<(), Error>> = forks
+        .par_iter_mut()
+        .zip(children.par_chunks(chunk_size))
+        .map(|((fragment, fork), nodes)| {
+            nodes
+                .iter()
+                .try_for_each(|node| render_node(fragment, node, base_transform, fork))
+        })
+        .collect();
+    for ((fragment, fork), result) in forks.into_iter().zip(results) {
+        cx.join(fork);
+        result?;
+        scene.append(&fragment, None);
+    }
+    Ok(())
+}
+
+fn render_node<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    node: &'t usvg::Node,
+    base_transform: Affine,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    cx.add_nodes(node, 1)?;
+    let Some(node_override) = cx.overrides.and_then(|overrides| overrides.get(node.id())) else {
+        return draw_node(scene, node, base_transform, None, cx);
+    };
+    if node_override.visible == Some(false) {
+        return Ok(());
+    }
+    let mut base_transform = base_transform;
+    if let Some(local_transform) = node_override.transform {
+        // Replace the transform of the node relative to its parent, for it and its descendants.
+        let abs_transform = util::to_affine(&node.abs_transform());
+        let old_local_transform = match node {
+            usvg::Node::Group(g) => util::to_affine(&g.transform()),
+            _ => Affine::IDENTITY,
+        };
+        base_transform = base_transform
+            * abs_transform
+            * old_local_transform.inverse()
+            * local_transform
+            * abs_transform.inverse();
+    }
+    let parent_fill = cx.fill;
+    cx.fill = node_override.fill.as_ref().or(parent_fill);
+    let result = match (node, node_override.opacity) {
+        (usvg::Node::Group(_), opacity) => draw_node(scene, node, base_transform, opacity, cx),
+        (_, Some(opacity)) => {
+            let transform = base_transform * util::to_affine(&node.abs_transform());
+            let bounds = util::to_rect(&node.stroke_bounding_box());
+            scene.push_layer(Fill::NonZero, Mix::Normal, opacity, transform, &bounds);
+            cx.enter_layer();
+            let result = draw_node(scene, node, base_transform, None, cx);
+            pop_group_layer(scene, cx);
+            result
+        }
+        (_, None) => draw_node(scene, node, base_transform, None, cx),
+    };
+    cx.fill = parent_fill;
+    result
+}
+
+/// Draw a node, replacing the opacity of groups with `opacity` if given.
+fn draw_node<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    node: &'t usvg::Node,
+    base_transform: Affine,
+    opacity: Option<f32>,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    if is_skipped(node, base_transform, cx) {
+        return Ok(());
+    }
+    let transform = base_transform * util::to_affine(&node.abs_transform());
+    if let Some(overlay) = &mut cx.overlay {
+        overlay.node(node, transform, cx.layers);
+    }
+    match node {
+        usvg::Node::Group(g) => {
+            if let Some((class, descendants)) = cx.instance_of(node, g) {
+                return draw_instance(scene, node, g, class, descendants, transform, opacity, cx);
+            }
+            push_group_layer(scene, node, g, transform, opacity, cx)?;
+            descend(scene, g, base_transform, Exit::Layer, cx)?;
+        }
+        usvg::Node::Path(path) => {
+            if !path.is_visible() {
+                return Ok(());
+            }
+            let (local_path, path_transform, brush_offset) =
+                cx.recenter(util::to_bez_path(path), transform);
+            let segments = local_path.elements().len();
+
+            let do_fill = |scene: &mut Scene, cx: &mut Context<'_, 't, H>| -> Result<(), Error> {
+                if let Some(fill) = &path.fill() {
+                    let brush = match cx.fill {
+                        Some(brush) => Some((brush.clone(), Affine::IDENTITY)),
+                        None => cx.to_brush(fill.paint(), fill.opacity()),
+                    };
+                    if let Some((brush, brush_transform)) = brush {
+                        cx.add_path_segments(node, segments)?;
+                        cx.draw(&brush);
+                        scene.fill(
+                            to_fill(fill.rule()),
+                            path_transform,
+                            &brush,
+                            Some(brush_offset * brush_transform),
+                            &local_path,
+                        );
+                    } else {
+                        cx.unsupported(scene, node, Unsupported::PatternPaint, transform)?;
                     }
-                    usvg::PaintOrder::StrokeAndFill => {
-                        do_stroke(scene, error_handler);
-                        do_fill(scene, error_handler);
+                }
+                Ok(())
+            };
+            let do_stroke = |scene: &mut Scene, cx: &mut Context<'_, 't, H>| -> Result<(), Error> {
+                if let Some(stroke) = &path.stroke() {
+                    if let Some((brush, brush_transform)) =
+                        cx.to_brush(stroke.paint(), stroke.opacity())
+                    {
+                        cx.add_path_segments(node, segments)?;
+                        cx.draw(&brush);
+                        let conv_stroke = util::to_stroke(stroke);
+                        scene.stroke(
+                            &conv_stroke,
+                            path_transform,
+                            &brush,
+                            Some(brush_offset * brush_transform),
+                            &local_path,
+                        );
+                    } else {
+                        cx.unsupported(scene, node, Unsupported::PatternPaint, transform)?;
                     }
                 }
-            }
-            usvg::Node::Image(img) => {
-                if !img.is_visible() {
-                    continue;
+                Ok(())
+            };
+            match path.paint_order() {
+                usvg::PaintOrder::FillAndStroke => {
+                    do_fill(scene, cx)?;
+                    do_stroke(scene, cx)?;
+                }
+                usvg::PaintOrder::StrokeAndFill => {
+                    do_stroke(scene, cx)?;
+                    do_fill(scene, cx)?;
                 }
-                match img.kind() {
-                    usvg::ImageKind::JPEG(_)
-                    | usvg::ImageKind::PNG(_)
-                    | usvg::ImageKind::GIF(_)
-                    | usvg::ImageKind::WEBP(_) => {
-                        #[cfg(feature = "image")]
-                        {
-                            let Ok(decoded_image) = util::decode_raw_raster_image(img.kind())
-                            else {
-                                error_handler(scene, node);
-                                continue;
-                            };
-                            let image = util::into_image(decoded_image);
-                            let image_ts = util::to_affine(&img.abs_transform());
-                            scene.draw_image(&image, image_ts);
+            }
+        }
+        usvg::Node::Image(img) => {
+            if !img.is_visible() {
+                return Ok(());
+            }
+            match img.kind() {
+                usvg::ImageKind::JPEG(_)
+                | usvg::ImageKind::PNG(_)
+                | usvg::ImageKind::GIF(_)
+                | usvg::ImageKind::WEBP(_) => {
+                    #[cfg(feature = "image")]
+                    {
+                        let Some(decoded_image) = cx.decode_image(node, img.kind())? else {
+                            cx.unsupported(scene, node, Unsupported::UndecodableImage, transform)?;
+                            return Ok(());
+                        };
+                        let image = util::into_image(decoded_image);
+                        cx.stats.images += 1;
+                        cx.stats.decoded_image_bytes += image.image.data.len();
+                        match cx.paint_override.as_ref().and_then(|p| p.image_tint()) {
+                            Some(tint) => {
+                                let bounds = Rect::new(
+                                    0.0,
+                                    0.0,
+                                    image.image.width.into(),
+                                    image.image.height.into(),
+                                );
+                                util::draw_tinted(scene, transform, &bounds, tint, |scene| {
+                                    scene.draw_image(&image, transform);
+                                });
+                            }
+                            None => scene.draw_image(&image, transform),
                         }
+                    }
 
-                        #[cfg(not(feature = "image"))]
-                        {
-                            error_handler(scene, node);
-                            continue;
-                        }
+                    #[cfg(not(feature = "image"))]
+                    {
+                        cx.unsupported(scene, node, Unsupported::MissingImageFeature, transform)?;
+                        return Ok(());
                     }
-                    usvg::ImageKind::SVG(svg) => {
-                        render_group(scene, svg.root(), transform, error_handler);
+                }
+                usvg::ImageKind::SVG(svg) => {
+                    if cx.nested_svgs >= cx.limits.max_nested_svg_depth {
+                        return Err(cx.exceeded(node.id(), Limit::NestedSvgDepth));
                     }
+                    cx.nested_svgs += 1;
+                    descend(scene, svg.root(), transform, Exit::NestedSvg, cx)?;
                 }
             }
-            usvg::Node::Text(text) => {
-                render_group(scene, text.flattened(), transform, error_handler);
+        }
+        usvg::Node::Text(text) => {
+            descend(scene, text.flattened(), transform, Exit::Nothing, cx)?;
+        }
+    }
+    Ok(())
+}
+
+/// Draw a group with the same content as other groups, see [`Context::instance_of`], by
+/// appending the fragment encoded for the first of them with `transform`.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "like `draw_node`, with the class of the group"
+)]
+fn draw_instance<'t, H: ErrorHandler>(
+    scene: &mut Scene,
+    node: &'t usvg::Node,
+    g: &'t usvg::Group,
+    class: usize,
+    descendants: usize,
+    transform: Affine,
+    opacity: Option<f32>,
+    cx: &mut Context<'_, 't, H>,
+) -> Result<(), Error> {
+    if let Some(segments) = cx
+        .fragments
+        .get(&class)
+        .map(|fragment| fragment.encoding().n_path_segments)
+    {
+        cx.add_nodes(node, descendants)?;
+        cx.add_path_segments(node, segments as usize)?;
+        if let Some(fragment) = cx.fragments.get(&class) {
+            scene.append(fragment, Some(transform));
+        }
+        cx.stats.instances += 1;
+        return Ok(());
+    }
+
+    // Draw the group in its own coordinate space, with the content of the group drawn directly
+    // and in full, as culling depends on where it is placed.
+    let mut fragment = Scene::new();
+    let base_transform = util::to_affine(&node.abs_transform()).inverse();
+    let frames = cx.frames.take();
+    let viewport = cx.viewport.take();
+    let level_of_detail = cx.level_of_detail.take();
+    cx.instancing = false;
+    let result = push_group_layer(&mut fragment, node, g, Affine::IDENTITY, opacity, cx)
+        .and_then(|()| descend(&mut fragment, g, base_transform, Exit::Layer, cx));
+    cx.frames = frames;
+    cx.viewport = viewport;
+    cx.level_of_detail = level_of_detail;
+    cx.instancing = true;
+
+    scene.append(&fragment, Some(transform));
+    if result.is_ok() {
+        cx.fragments.insert(class, fragment);
+    }
+    result
+}
+
+/// Whether `node` is outside of the viewport or too small to be seen, in which case it is
+/// counted as such.
+fn is_skipped<H: ErrorHandler>(
+    node: &usvg::Node,
+    base_transform: Affine,
+    cx: &mut Context<'_, '_, H>,
+) -> bool {
+    // Changed nodes can be moved and scaled beyond the bounds of their ancestors.
+    if cx.viewport.is_none() && cx.level_of_detail.is_none() || cx.overrides.is_some() {
+        return false;
+    }
+    let bounds = base_transform.transform_rect_bbox(drawn_bounds(node));
+    if cx
+        .viewport
+        .is_some_and(|viewport| bounds.intersect(viewport).is_zero_area())
+    {
+        cx.stats.culled_nodes += 1;
+        return true;
+    }
+    if cx
+        .level_of_detail
+        .is_some_and(|lod| bounds.width().max(bounds.height()) * lod.scale < lod.min_size)
+    {
+        cx.stats.small_nodes += 1;
+        return true;
+    }
+    false
+}
+
+/// The bounds of everything `node` draws, including strokes and filter regions, in the coordinate
+/// space of its document.
+fn drawn_bounds(node: &usvg::Node) -> Rect {
+    match node {
+        usvg::Node::Group(g) => util::to_rect(&g.abs_layer_bounding_box().to_rect()),
+        // Unlike for groups, the layer bounds of other nodes don't include their stroke.
+        _ => util::to_rect(&node.abs_stroke_bounding_box()),
+    }
+}
+
+/// Push the layer for a group, after reporting the features of it which can't be drawn exactly.
+///
+/// `opacity` replaces the opacity of the group if given.
+fn push_group_layer<H: ErrorHandler>(
+    scene: &mut Scene,
+    node: &usvg::Node,
+    g: &usvg::Group,
+    transform: Affine,
+    opacity: Option<f32>,
+    cx: &mut Context<'_, '_, H>,
+) -> Result<(), Error> {
+    if g.mask().is_some() {
+        cx.unsupported(scene, node, Unsupported::Mask, transform)?;
+    }
+    if !g.filters().is_empty() {
+        cx.unsupported(scene, node, Unsupported::Filter, transform)?;
+    }
+    if let Some(clip_path) = g.clip_path()
+        && !is_simple_clip_path(clip_path)
+    {
+        cx.unsupported(scene, node, Unsupported::ClipPath, transform)?;
+    }
+
+    let alpha = opacity.unwrap_or_else(|| g.opacity().get());
+    let blend_mode: BlendMode = match g.blend_mode() {
+        usvg::BlendMode::Normal => Mix::Normal.into(),
+        usvg::BlendMode::Multiply => Mix::Multiply.into(),
+        usvg::BlendMode::Screen => Mix::Screen.into(),
+        usvg::BlendMode::Overlay => Mix::Overlay.into(),
+        usvg::BlendMode::Darken => Mix::Darken.into(),
+        usvg::BlendMode::Lighten => Mix::Lighten.into(),
+        usvg::BlendMode::ColorDodge => Mix::ColorDodge.into(),
+        usvg::BlendMode::ColorBurn => Mix::ColorBurn.into(),
+        usvg::BlendMode::HardLight => Mix::HardLight.into(),
+        usvg::BlendMode::SoftLight => Mix::SoftLight.into(),
+        usvg::BlendMode::Difference => Mix::Difference.into(),
+        usvg::BlendMode::Exclusion => Mix::Exclusion.into(),
+        usvg::BlendMode::Hue => Mix::Hue.into(),
+        usvg::BlendMode::Saturation => Mix::Saturation.into(),
+        usvg::BlendMode::Color => Mix::Color.into(),
+        usvg::BlendMode::Luminosity => Mix::Luminosity.into(),
+    };
+
+    match g
+        .clip_path()
+        // support clip-path with a single path
+        .and_then(|path| path.root().children().first())
+    {
+        Some(usvg::Node::Path(clip_path)) => {
+            let (local_path, transform, _) = cx.recenter(util::to_bez_path(clip_path), transform);
+            scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &local_path);
+            if let Some(overlay) = &mut cx.overlay {
+                overlay.clip_path(&local_path, transform);
+            }
+        }
+        _ => {
+            // Use bounding box as the clip path, unless nodes can be moved outside of it.
+            let rect = if cx.overrides.is_some() && g.clip_path().is_none() {
+                UNBOUNDED
+            } else {
+                util::to_rect(&g.layer_bounding_box().to_rect())
+            };
+            if cx.recenter && rect != UNBOUNDED {
+                let (local_rect, transform, _) = cx.recenter(rect.to_path(0.1), transform);
+                scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &local_rect);
+            } else {
+                scene.push_layer(Fill::NonZero, blend_mode, alpha, transform, &rect);
+            }
+            if let Some(overlay) = &mut cx.overlay
+                && g.clip_path().is_some()
+            {
+                overlay.clip_path(&rect, transform);
             }
         }
     }
+    cx.enter_layer();
+    Ok(())
+}
+
+fn pop_group_layer<H: ErrorHandler>(scene: &mut Scene, cx: &mut Context<'_, '_, H>) {
+    cx.layers -= 1;
+    scene.pop_layer();
+}
+
+/// Whether a clip path is drawn exactly, i.e. consists of a single path in the coordinate space
+/// of the clipped group.
+pub(crate) fn is_simple_clip_path(clip_path: &usvg::ClipPath) -> bool {
+    clip_path.transform().is_identity()
+        && clip_path.clip_path().is_none()
+        && matches!(clip_path.root().children(), [usvg::Node::Path(_)])
 }
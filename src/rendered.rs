@@ -0,0 +1,67 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{RenderReport, RenderStats, util};
+use vello::Scene;
+use vello::kurbo::{Rect, Size};
+
+/// An SVG document which has been rendered to a vello [`Scene`], together with the
+/// information needed to lay it out.
+///
+/// This doesn't borrow from the [`usvg::Tree`] it was rendered from, so it can be cached
+/// independently of it.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct RenderedSvg {
+    /// The rendered scene.
+    pub scene: Scene,
+    /// The intrinsic size of the document, i.e. its `width` and `height`.
+    pub size: Size,
+    /// The `viewBox` of the document's root element, if it has one.
+    ///
+    /// This is only known when rendering from source; a [`usvg::Tree`] has already applied the
+    /// view box to its root transform, so rendering from a tree always leaves this as `None`.
+    pub view_box: Option<Rect>,
+    /// The bounds of the content of the document, including strokes and filter regions,
+    /// in the coordinate space of the scene.
+    ///
+    /// This is in the same coordinate space as [`size`](Self::size), unless the document was
+    /// moved by [`RenderOptions::origin`](crate::RenderOptions::origin).
+    pub bounds: Rect,
+    /// The features of the document which could not be rendered exactly.
+    pub report: RenderReport,
+    /// Statistics about how the document was encoded.
+    pub stats: RenderStats,
+}
+
+impl RenderedSvg {
+    pub(crate) fn new(
+        scene: Scene,
+        tree: &usvg::Tree,
+        view_box: Option<Rect>,
+        report: RenderReport,
+        stats: RenderStats,
+    ) -> Self {
+        let size = tree.size();
+        Self {
+            scene,
+            size: Size::new(size.width() as f64, size.height() as f64),
+            view_box,
+            bounds: util::to_rect(&tree.root().abs_layer_bounding_box().to_rect()),
+            report,
+            stats,
+        }
+    }
+}
+
+impl std::fmt::Debug for RenderedSvg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderedSvg")
+            .field("size", &self.size)
+            .field("view_box", &self.view_box)
+            .field("bounds", &self.bounds)
+            .field("report", &self.report)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
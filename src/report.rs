@@ -0,0 +1,173 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::render;
+use crate::{Limit, Unsupported};
+
+#[cfg(feature = "image")]
+use crate::util;
+
+/// The features of a document which can't be rendered exactly.
+///
+/// This is returned by [`analyze`](crate::analyze), and alongside rendered documents in
+/// [`RenderedSvg::report`](crate::RenderedSvg::report).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderReport {
+    /// Each unsupported feature found in the document, in the order they were first encountered.
+    pub features: Vec<FeatureReport>,
+    /// The limit which was exceeded, if any, in which case the rest of the document
+    /// was not rendered or analyzed.
+    pub exceeded_limit: Option<Limit>,
+}
+
+/// The occurrences of one unsupported feature in a document, see [`RenderReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureReport {
+    /// The feature.
+    pub feature: Unsupported,
+    /// The number of times the feature was encountered.
+    ///
+    /// A single node can be counted more than once, e.g. if both its fill and stroke use a pattern.
+    pub count: usize,
+    /// The ids of the nodes using this feature, in document order.
+    ///
+    /// Nodes without an id are only included in [`count`](Self::count).
+    pub node_ids: Vec<String>,
+}
+
+impl RenderReport {
+    /// Whether the whole document can be rendered exactly.
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.exceeded_limit.is_none()
+    }
+
+    /// Whether some content of the document is left out entirely, rather than drawn approximately.
+    pub fn has_omissions(&self) -> bool {
+        self.exceeded_limit.is_some()
+            || self
+                .features
+                .iter()
+                .any(|report| report.feature.is_omitted())
+    }
+
+    /// The number of times `feature` was encountered.
+    pub fn count(&self, feature: Unsupported) -> usize {
+        self.get(feature).map_or(0, |report| report.count)
+    }
+
+    /// The occurrences of `feature`, if it was encountered.
+    pub fn get(&self, feature: Unsupported) -> Option<&FeatureReport> {
+        self.features
+            .iter()
+            .find(|report| report.feature == feature)
+    }
+
+    /// Find the features of the descendants of `root` which can't be rendered exactly, in the
+    /// order rendering encounters them, without encoding anything.
+    pub(crate) fn analyze(root: &usvg::Group) -> Self {
+        let mut report = Self::default();
+        // Walk the tree with an explicit stack, as documents can be nested arbitrarily deep.
+        let mut stack = vec![root.children().iter()];
+        while let Some(children) = stack.last_mut() {
+            let Some(node) = children.next() else {
+                stack.pop();
+                continue;
+            };
+            match node {
+                usvg::Node::Group(g) => {
+                    if g.mask().is_some() {
+                        report.record(Unsupported::Mask, node.id());
+                    }
+                    if !g.filters().is_empty() {
+                        report.record(Unsupported::Filter, node.id());
+                    }
+                    if g.clip_path()
+                        .is_some_and(|clip_path| !render::is_simple_clip_path(clip_path))
+                    {
+                        report.record(Unsupported::ClipPath, node.id());
+                    }
+                    stack.push(g.children().iter());
+                }
+                usvg::Node::Path(path) => {
+                    if !path.is_visible() {
+                        continue;
+                    }
+                    let fill = path.fill().map(usvg::Fill::paint);
+                    let stroke = path.stroke().map(usvg::Stroke::paint);
+                    for paint in [fill, stroke].into_iter().flatten() {
+                        if matches!(paint, usvg::Paint::Pattern(_)) {
+                            report.record(Unsupported::PatternPaint, node.id());
+                        }
+                    }
+                }
+                usvg::Node::Image(image) => {
+                    if !image.is_visible() {
+                        continue;
+                    }
+                    match image.kind() {
+                        usvg::ImageKind::SVG(svg) => stack.push(svg.root().children().iter()),
+                        #[cfg(feature = "image")]
+                        kind => {
+                            if util::raster_image_dimensions(kind).is_err() {
+                                report.record(Unsupported::UndecodableImage, node.id());
+                            }
+                        }
+                        #[cfg(not(feature = "image"))]
+                        _ => report.record(Unsupported::MissingImageFeature, node.id()),
+                    }
+                }
+                usvg::Node::Text(text) => stack.push(text.flattened().children().iter()),
+            }
+        }
+        report
+    }
+
+    pub(crate) fn record(&mut self, feature: Unsupported, id: &str) {
+        let report = self.entry(feature);
+        report.count += 1;
+        // A node is reported repeatedly only for its fill and stroke, which happen in a row.
+        if !id.is_empty() && report.node_ids.last().is_none_or(|last| last != id) {
+            report.node_ids.push(id.to_owned());
+        }
+    }
+
+    /// Add the report of the nodes following those in this report, which were encoded separately.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn merge(&mut self, other: Self) {
+        for other_report in other.features {
+            let report = self.entry(other_report.feature);
+            report.count += other_report.count;
+            let mut ids = other_report.node_ids.into_iter().peekable();
+            if report
+                .node_ids
+                .last()
+                .is_some_and(|last| ids.peek() == Some(last))
+            {
+                ids.next();
+            }
+            report.node_ids.extend(ids);
+        }
+        self.exceeded_limit = self.exceeded_limit.or(other.exceeded_limit);
+    }
+
+    fn entry(&mut self, feature: Unsupported) -> &mut FeatureReport {
+        let index = match self
+            .features
+            .iter()
+            .position(|report| report.feature == feature)
+        {
+            Some(index) => index,
+            None => {
+                self.features.push(FeatureReport {
+                    feature,
+                    count: 0,
+                    node_ids: Vec::new(),
+                });
+                self.features.len() - 1
+            }
+        };
+        &mut self.features[index]
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{DefaultErrorHandler, Error, RenderOptions, render};
+use std::collections::{HashMap, HashSet};
+use vello::Scene;
+use vello::kurbo::Affine;
+use vello::peniko::Brush;
+
+/// An SVG document whose nodes can be changed by id, which only re-encodes the parts of the
+/// document that changed.
+///
+/// The document is split into fragments, one for each of the top level elements of the
+/// document, which are encoded separately and cached until a node inside of them changes. Groups
+/// which wrap all other elements, such as the layer groups of drawing programs, are looked into
+/// to find the top level elements; changing such a group changes every fragment.
+///
+/// This uses default error handling, so draws a red box over (some) unsupported elements.
+pub struct RetainedSvg {
+    tree: usvg::Tree,
+    options: RenderOptions,
+    overrides: HashMap<String, NodeOverride>,
+    /// The encoded fragments, which are `None` when they need to be encoded again.
+    fragments: Vec<Option<Scene>>,
+    /// The index of the fragment containing each node with an id.
+    fragment_of: HashMap<String, usize>,
+    /// The ids of the groups which can wrap all fragments, see `fragment_container`.
+    wrappers: HashSet<String>,
+}
+
+/// The changes made to a node of a [`RetainedSvg`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NodeOverride {
+    pub(crate) fill: Option<Brush>,
+    pub(crate) transform: Option<Affine>,
+    pub(crate) visible: Option<bool>,
+    pub(crate) opacity: Option<f32>,
+}
+
+impl RetainedSvg {
+    /// Retain a [`usvg::Tree`], with default options.
+    pub fn new(tree: usvg::Tree) -> Self {
+        Self::with_options(tree, RenderOptions::default())
+    }
+
+    /// Retain a [`usvg::Tree`], with [`RenderOptions`].
+    pub fn with_options(tree: usvg::Tree, options: RenderOptions) -> Self {
+        let overrides = HashMap::new();
+        let (path, _) = fragment_container(&tree, &overrides);
+        let wrappers = path
+            .iter()
+            .filter(|node| !node.id().is_empty())
+            .map(|node| node.id().to_owned())
+            .collect();
+        let mut svg = Self {
+            tree,
+            options,
+            overrides,
+            fragments: Vec::new(),
+            fragment_of: HashMap::new(),
+            wrappers,
+        };
+        svg.split();
+        svg
+    }
+
+    /// The retained document, without any of the changes made to it.
+    pub fn tree(&self) -> &usvg::Tree {
+        &self.tree
+    }
+
+    /// Replace the paint of the fills of the node with the given `id` and its descendants.
+    ///
+    /// Nodes which aren't filled stay that way.
+    pub fn set_fill(&mut self, id: &str, fill: impl Into<Brush>) -> Result<(), Error> {
+        self.change(id, |node| node.fill = Some(fill.into()))
+    }
+
+    /// Replace the transform of the node with the given `id`, relative to its parent.
+    pub fn set_transform(&mut self, id: &str, transform: Affine) -> Result<(), Error> {
+        self.change(id, |node| node.transform = Some(transform))
+    }
+
+    /// Show or hide the node with the given `id` and its descendants.
+    pub fn set_visible(&mut self, id: &str, visible: bool) -> Result<(), Error> {
+        self.change(id, |node| node.visible = Some(visible))
+    }
+
+    /// Replace the opacity of the node with the given `id`.
+    pub fn set_opacity(&mut self, id: &str, opacity: f32) -> Result<(), Error> {
+        self.change(id, |node| node.opacity = Some(opacity))
+    }
+
+    /// Undo all changes to the node with the given `id`.
+    pub fn reset(&mut self, id: &str) -> Result<(), Error> {
+        if self.wrappers.contains(id) {
+            if self.overrides.remove(id).is_some() {
+                self.split();
+            }
+            return Ok(());
+        }
+        let fragment = self.fragment(id)?;
+        if self.overrides.remove(id).is_some() {
+            self.fragments[fragment] = None;
+        }
+        Ok(())
+    }
+
+    /// Whether some fragments need to be encoded again before the document is appended.
+    pub fn is_dirty(&self) -> bool {
+        self.fragments.iter().any(Option::is_none)
+    }
+
+    /// Append the document to `scene` with `transform` applied, after encoding the fragments
+    /// which changed.
+    ///
+    /// Nothing is appended if encoding a fragment fails, see [`update`](Self::update).
+    pub fn append_to(&mut self, scene: &mut Scene, transform: Affine) -> Result<(), Error> {
+        self.update()?;
+        for fragment in self.fragments.iter().flatten() {
+            scene.append(fragment, Some(transform));
+        }
+        Ok(())
+    }
+
+    /// Encode the fragments which changed since they were last encoded.
+    ///
+    /// This fails when a fragment exceeds the [`Limits`](crate::Limits) of the options, which
+    /// leaves that fragment to be encoded again.
+    pub fn update(&mut self) -> Result<(), Error> {
+        let (mut path, container) = fragment_container(&self.tree, &self.overrides);
+        for (fragment, node) in self.fragments.iter_mut().zip(container.children()) {
+            if fragment.is_some() {
+                continue;
+            }
+            path.push(node);
+            let mut scene = Scene::new();
+            let mut cx = render::Context::new(&scene, &self.options, DefaultErrorHandler);
+            cx.overrides = Some(&self.overrides);
+            let result = render::render_path(
+                &mut scene,
+                self.tree.root(),
+                &path,
+                self.options.origin_transform(),
+                &mut cx,
+            );
+            cx.finish(&mut scene);
+            result?;
+            *fragment = Some(scene);
+            path.pop();
+        }
+        Ok(())
+    }
+
+    fn change(&mut self, id: &str, f: impl FnOnce(&mut NodeOverride)) -> Result<(), Error> {
+        if self.wrappers.contains(id) {
+            f(self.overrides.entry(id.to_owned()).or_default());
+            self.split();
+            return Ok(());
+        }
+        let fragment = self.fragment(id)?;
+        f(self.overrides.entry(id.to_owned()).or_default());
+        self.fragments[fragment] = None;
+        Ok(())
+    }
+
+    /// Split the document into fragments again, which all need to be encoded, after a group
+    /// wrapping them changed.
+    fn split(&mut self) {
+        let (_, container) = fragment_container(&self.tree, &self.overrides);
+        self.fragment_of.clear();
+        for (index, node) in container.children().iter().enumerate() {
+            collect_ids(node, index, &mut self.fragment_of);
+        }
+        self.fragments = vec![None; container.children().len()];
+    }
+
+    fn fragment(&self, id: &str) -> Result<usize, Error> {
+        self.fragment_of
+            .get(id)
+            .copied()
+            .ok_or_else(|| Error::NodeNotFound { id: id.to_owned() })
+    }
+}
+
+impl std::fmt::Debug for RetainedSvg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetainedSvg")
+            .field("fragments", &self.fragments.len())
+            .field("changed_nodes", &self.overrides.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Find the group whose children are the fragments of the document, and the nodes leading to it
+/// from the root group, as expected by [`render::render_path`].
+///
+/// This skips groups which are the only child of their parent and can be drawn separately around
+/// each fragment without changing the output, such as the group usvg creates for the view box, or
+/// the layer groups of drawing programs. Groups which are changed aren't skipped, because the
+/// changes are only applied to the fragments themselves.
+fn fragment_container<'t>(
+    tree: &'t usvg::Tree,
+    overrides: &HashMap<String, NodeOverride>,
+) -> (Vec<&'t usvg::Node>, &'t usvg::Group) {
+    let mut path = Vec::new();
+    let mut group = tree.root();
+    while let [node @ usvg::Node::Group(child)] = group.children()
+        && !overrides.contains_key(child.id())
+        && child.opacity().get() == 1.0
+        && child.blend_mode() == usvg::BlendMode::Normal
+        && child.clip_path().is_none()
+        && child.mask().is_none()
+        && child.filters().is_empty()
+    {
+        path.push(node);
+        group = child;
+    }
+    (path, group)
+}
+
+fn collect_ids(node: &usvg::Node, fragment: usize, ids: &mut HashMap<String, usize>) {
+    if !node.id().is_empty() {
+        ids.insert(node.id().to_owned(), fragment);
+    }
+    if let usvg::Node::Group(group) = node {
+        for child in group.children() {
+            collect_ids(child, fragment, ids);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{DefaultErrorHandler, Error, ErrorHandler, RenderOptions, render, util};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use vello::Scene;
+use vello::kurbo::{Affine, Rect};
+use vello::peniko::Color;
+
+/// The `<symbol>` elements of an SVG document, such as an icon library, each encoded once into
+/// its own [`Sprite`].
+#[derive(Debug, Clone, Default)]
+pub struct SpriteSheet {
+    sprites: HashMap<String, Sprite>,
+}
+
+/// A single `<symbol>` of a [`SpriteSheet`].
+#[derive(Clone)]
+pub struct Sprite {
+    /// The encoded content of the symbol, in the coordinate space of its view box, but moved so
+    /// that the view box starts at the origin.
+    pub scene: Scene,
+    /// The `viewBox` of the symbol, or the bounds of its content if it doesn't have one.
+    pub view_box: Rect,
+}
+
+impl SpriteSheet {
+    /// Parse an SVG document and encode each of its `<symbol>` elements which has an id,
+    /// with default error handling.
+    ///
+    /// This will draw a red box over (some) unsupported elements.
+    pub fn new(svg: &str) -> Result<Self, Error> {
+        Self::with_handler(svg, &usvg::Options::default(), &mut DefaultErrorHandler)
+    }
+
+    /// Parse an SVG document and encode each of its `<symbol>` elements which has an id,
+    /// with an [`ErrorHandler`].
+    ///
+    /// Returns the first error returned by the handler, or an error if a symbol exceeds a limit.
+    pub fn with_handler<H: ErrorHandler + ?Sized>(
+        svg: &str,
+        opt: &usvg::Options<'_>,
+        error_handler: &mut H,
+    ) -> Result<Self, Error> {
+        let xml_opt = usvg::roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        };
+        let doc = usvg::roxmltree::Document::parse_with_options(svg, xml_opt)
+            .map_err(usvg::Error::ParsingFailed)?;
+        let symbols: Vec<(&str, Option<Rect>)> = doc
+            .descendants()
+            .filter(|node| node.has_tag_name("symbol"))
+            .filter_map(|node| {
+                let id = node.attribute("id").filter(|id| !id.is_empty())?;
+                let view_box = node.attribute("viewBox").and_then(crate::parse_view_box);
+                Some((id, view_box))
+            })
+            .collect();
+        let root = doc.root_element();
+        let element = &svg[root.range()];
+        let Some(end) = end_tag_start(element) else {
+            // The root element is empty, so there are no symbols.
+            return Ok(Self::default());
+        };
+        let end = root.range().start + end;
+        // The `<use>` elements need the same namespace as the root element.
+        let name = util::start_tag_name(element);
+        let prefix = name.rfind(':').map_or("", |colon| &name[..=colon]);
+        let use_ids = unique_id_prefix(&doc);
+
+        // Symbols are only drawn when they are used, so instantiate each of them at the end of
+        // the document, in the coordinate space of its view box.
+        let mut source = String::with_capacity(svg.len() + symbols.len() * 64);
+        source.push_str(&svg[..end]);
+        for (index, (id, view_box)) in symbols.iter().enumerate() {
+            write!(
+                source,
+                r##"<{prefix}use id="{use_ids}{index}" href="#{}""##,
+                escape(id)
+            )
+            .unwrap();
+            if let Some(view_box) = view_box {
+                write!(
+                    source,
+                    r#" width="{}" height="{}""#,
+                    view_box.width(),
+                    view_box.height()
+                )
+                .unwrap();
+            }
+            source.push_str("/>");
+        }
+        source.push_str(&svg[end..]);
+        let tree = usvg::Tree::from_str(&source, opt)?;
+
+        let mut sprites = HashMap::with_capacity(symbols.len());
+        for (index, (id, view_box)) in symbols.into_iter().enumerate() {
+            let Some(node) = tree.node_by_id(&format!("{use_ids}{index}")) else {
+                // The symbol has no content to draw.
+                continue;
+            };
+            // Draw the node without the transforms of its ancestors, e.g. the view box of the
+            // document.
+            let parent_transform = util::to_affine(&node.abs_transform())
+                * util::to_affine(&local_transform(node)).inverse();
+            let base_transform = parent_transform.inverse();
+            let view_box = view_box.map_or_else(
+                || {
+                    let bounds = node
+                        .abs_layer_bounding_box()
+                        .map_or(Rect::ZERO, |rect| util::to_rect(&rect.to_rect()));
+                    base_transform.transform_rect_bbox(bounds)
+                },
+                |view_box| view_box.with_origin((0.0, 0.0)),
+            );
+
+            let mut scene = Scene::new();
+            let mut cx = render::Context::new(
+                &scene,
+                &RenderOptions::default(),
+                render::Borrowed(&mut *error_handler),
+            );
+            let transform = Affine::translate(-view_box.origin().to_vec2()) * base_transform;
+            let result = render::render_path(&mut scene, tree.root(), &[node], transform, &mut cx);
+            cx.finish(&mut scene);
+            result?;
+            sprites.insert(
+                id.to_owned(),
+                Sprite {
+                    scene,
+                    view_box: view_box.with_origin((0.0, 0.0)),
+                },
+            );
+        }
+        Ok(Self { sprites })
+    }
+
+    /// The sprite for the symbol with the given `id`.
+    pub fn get(&self, id: &str) -> Option<&Sprite> {
+        self.sprites.get(id)
+    }
+
+    /// The ids of the symbols in the sheet, in no particular order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.sprites.keys().map(String::as_str)
+    }
+
+    /// The number of symbols in the sheet.
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    /// Whether the sheet has no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+
+    /// Append the symbol with the given `id` to `scene`, see [`Sprite::append_to`].
+    ///
+    /// Returns [`Error::NodeNotFound`] if there is no such symbol.
+    pub fn append(
+        &self,
+        scene: &mut Scene,
+        id: &str,
+        rect: Rect,
+        color: Option<Color>,
+    ) -> Result<(), Error> {
+        let sprite = self
+            .get(id)
+            .ok_or_else(|| Error::NodeNotFound { id: id.to_owned() })?;
+        sprite.append_to(scene, rect, color);
+        Ok(())
+    }
+}
+
+impl Sprite {
+    /// Append the sprite to `scene`, scaled uniformly to fit inside of `rect` and centered in it.
+    ///
+    /// If `color` is given, every part of the sprite is drawn in that color, keeping its shape and
+    /// transparency, as is usual for monochrome icons.
+    pub fn append_to(&self, scene: &mut Scene, rect: Rect, color: Option<Color>) {
+        let transform = self.transform(rect);
+        let Some(color) = color else {
+            scene.append(&self.scene, Some(transform));
+            return;
+        };
+        util::draw_tinted(scene, transform, &self.view_box, color, |scene| {
+            scene.append(&self.scene, Some(transform));
+        });
+    }
+
+    /// The transform which fits the view box inside of `rect`, preserving its aspect ratio.
+    pub fn transform(&self, rect: Rect) -> Affine {
+        let (width, height) = (self.view_box.width(), self.view_box.height());
+        if width <= 0.0 || height <= 0.0 {
+            return Affine::translate(rect.center().to_vec2());
+        }
+        let scale = (rect.width() / width).min(rect.height() / height);
+        let offset = rect.center().to_vec2() - (self.view_box.center().to_vec2() * scale);
+        Affine::translate(offset) * Affine::scale(scale)
+    }
+}
+
+impl std::fmt::Debug for Sprite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sprite")
+            .field("view_box", &self.view_box)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The offset of the end tag of an element in its source text, or `None` if the element is
+/// self-closing.
+fn end_tag_start(element: &str) -> Option<usize> {
+    let rest = element.strip_suffix('>')?.trim_end();
+    let rest = rest.strip_suffix(util::start_tag_name(element))?;
+    Some(rest.strip_suffix("</")?.len())
+}
+
+/// A prefix for the ids of the `<use>` elements, which no id in the document starts with.
+fn unique_id_prefix(doc: &usvg::roxmltree::Document<'_>) -> String {
+    let ids: Vec<&str> = doc
+        .descendants()
+        .filter_map(|node| node.attribute("id"))
+        .collect();
+    let mut attempt = 0_usize;
+    loop {
+        let prefix = format!("vello_svg-sprite{attempt}-");
+        if !ids.iter().any(|id| id.starts_with(&prefix)) {
+            return prefix;
+        }
+        attempt += 1;
+    }
+}
+
+fn local_transform(node: &usvg::Node) -> usvg::Transform {
+    match node {
+        usvg::Node::Group(group) => group.transform(),
+        _ => usvg::Transform::identity(),
+    }
+}
+
+/// Escape a string for use in a double-quoted XML attribute.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
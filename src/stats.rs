@@ -0,0 +1,91 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::time::{Duration, Instant};
+
+/// Statistics about the encoding of a document, returned in
+/// [`RenderedSvg::stats`](crate::RenderedSvg::stats).
+///
+/// Counts include nested SVG images and text, which are drawn as groups of paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderStats {
+    /// The number of groups visited, including the root group of the document.
+    pub groups: usize,
+    /// The number of layers pushed.
+    pub layers: usize,
+    /// The deepest nesting of layers.
+    pub max_layer_depth: usize,
+    /// The number of fills and strokes drawn.
+    ///
+    /// A path with both a fill and a stroke is counted twice.
+    pub paths: usize,
+    /// The number of path segments encoded, including those of layer clips and anything drawn
+    /// by the error handler.
+    pub path_segments: usize,
+    /// The number of color stops in the gradients drawn.
+    pub gradient_stops: usize,
+    /// The number of nodes skipped because they are outside of
+    /// [`RenderOptions::viewport`](crate::RenderOptions::viewport), without their descendants.
+    pub culled_nodes: usize,
+    /// The number of nodes skipped because they are smaller than
+    /// [`RenderOptions::level_of_detail`](crate::RenderOptions::level_of_detail) allows, without
+    /// their descendants.
+    pub small_nodes: usize,
+    /// The number of groups drawn by appending the encoding of an earlier group with the same
+    /// content, see [`RenderOptions::instancing`](crate::RenderOptions::instancing).
+    ///
+    /// The content of these groups isn't counted in the other statistics, except for path
+    /// segments.
+    pub instances: usize,
+    /// The number of paths drawn as part of the fill of an earlier sibling, see
+    /// [`RenderOptions::merge_paths`](crate::RenderOptions::merge_paths).
+    pub merged_paths: usize,
+    /// The number of raster images drawn.
+    pub images: usize,
+    /// The size of the raster images drawn, once decoded.
+    pub decoded_image_bytes: usize,
+    /// The time taken to parse the document, if it was parsed by this crate.
+    ///
+    /// Timings are not measured on `wasm32` targets, where they are always `None`.
+    pub parse_time: Option<Duration>,
+    /// The time taken to encode the document into a scene.
+    ///
+    /// Timings are not measured on `wasm32` targets, where they are always `None`.
+    pub encode_time: Option<Duration>,
+}
+
+impl RenderStats {
+    /// Add the counts of `other`, which was encoded separately, to these.
+    ///
+    /// Path segments and timings are measured for the whole scene instead.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.groups += other.groups;
+        self.layers += other.layers;
+        self.max_layer_depth = self.max_layer_depth.max(other.max_layer_depth);
+        self.paths += other.paths;
+        self.gradient_stops += other.gradient_stops;
+        self.culled_nodes += other.culled_nodes;
+        self.small_nodes += other.small_nodes;
+        self.instances += other.instances;
+        self.merged_paths += other.merged_paths;
+        self.images += other.images;
+        self.decoded_image_bytes += other.decoded_image_bytes;
+    }
+}
+
+/// A point in time to measure durations from, which does nothing on targets without a clock.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer(Option<Instant>);
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        // `Instant::now` panics on `wasm32-unknown-unknown`.
+        Self((!cfg!(target_arch = "wasm32")).then(Instant::now))
+    }
+
+    pub(crate) fn elapsed(self) -> Option<Duration> {
+        self.0.map(|start| start.elapsed())
+    }
+}
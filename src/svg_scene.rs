@@ -0,0 +1,67 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::RenderedSvg;
+use std::sync::Arc;
+use vello::Scene;
+use vello::kurbo::{Affine, Rect, Size};
+
+/// An SVG document which has been encoded once, to be appended to scenes any number of times.
+///
+/// Appending this is much cheaper than [`append_tree`](crate::append_tree), which converts the
+/// whole document again each time, and gives the same output. It is cheap to clone, and can be
+/// shared between threads.
+#[derive(Clone)]
+pub struct SvgScene {
+    scene: Arc<Scene>,
+    size: Size,
+    bounds: Rect,
+}
+
+impl SvgScene {
+    /// Encode a [`usvg::Tree`], with default error handling.
+    ///
+    /// This will draw a red box over (some) unsupported elements.
+    pub fn new(svg: &usvg::Tree) -> Self {
+        crate::render_tree(svg).into()
+    }
+
+    /// Append the document to `scene`, with `transform` applied.
+    pub fn append_to(&self, scene: &mut Scene, transform: Affine) {
+        scene.append(&self.scene, Some(transform));
+    }
+
+    /// The encoded document.
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    /// The intrinsic size of the document, see [`RenderedSvg::size`].
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// The bounds of the content of the document, see [`RenderedSvg::bounds`].
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+impl From<RenderedSvg> for SvgScene {
+    fn from(rendered: RenderedSvg) -> Self {
+        Self {
+            scene: Arc::new(rendered.scene),
+            size: rendered.size,
+            bounds: rendered.bounds,
+        }
+    }
+}
+
+impl std::fmt::Debug for SvgScene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SvgScene")
+            .field("size", &self.size)
+            .field("bounds", &self.bounds)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{Budget, Error, IncrementalRender, RenderOptions, RenderedSvg};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// The number of nodes encoded between checks for cancellation.
+const NODES_PER_STEP: usize = 256;
+
+/// Load, parse and render SVG data on a background thread, with default error handling.
+///
+/// `load` is called on the background thread, so that reading the data doesn't block the calling
+/// thread either, e.g. `move || std::fs::read(path)`. Data which is already loaded can be passed
+/// as `move || Ok::<_, vello_svg::Error>(data)`. Errors returned by `load` finish the task.
+///
+/// Like [`render_bytes`](crate::render_bytes), this also accepts gzip-compressed (`.svgz`)
+/// data. Relative paths in the document are resolved with [`usvg::Options::resources_dir`].
+///
+/// This is not available on `wasm32` targets, where [`IncrementalRender`] can spread the
+/// encoding over several frames instead.
+pub fn render_async<D, E>(
+    load: impl FnOnce() -> Result<D, E> + Send + 'static,
+    opt: usvg::Options<'static>,
+    options: RenderOptions,
+) -> RenderTask
+where
+    D: AsRef<[u8]>,
+    E: Into<Error>,
+{
+    RenderTask::spawn(move || load().map_err(Into::into), opt, options)
+}
+
+/// An SVG document being parsed and rendered on a background thread, see [`render_async`].
+///
+/// Dropping the task cancels it. If rendering panics on the background thread, the panic is
+/// resumed by the method which would have received the result.
+pub struct RenderTask {
+    shared: Arc<Shared>,
+    receiver: Receiver<Result<RenderedSvg, Error>>,
+    thread: Option<JoinHandle<()>>,
+    /// The result, once it has been received but not taken.
+    result: Option<Result<RenderedSvg, Error>>,
+}
+
+/// The state shared with the background thread.
+#[derive(Debug, Default)]
+struct Shared {
+    cancelled: AtomicBool,
+    /// The bits of the `f32` returned by [`RenderTask::progress`].
+    progress: AtomicU32,
+}
+
+impl RenderTask {
+    fn spawn<D: AsRef<[u8]>>(
+        load: impl FnOnce() -> Result<D, Error> + Send + 'static,
+        opt: usvg::Options<'static>,
+        options: RenderOptions,
+    ) -> Self {
+        let shared = Arc::new(Shared::default());
+        let (sender, receiver) = mpsc::channel();
+        let thread = {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || {
+                // The task may have been dropped already, in which case nobody is interested.
+                let result = load().and_then(|svg| run(svg.as_ref(), &opt, &options, &shared));
+                sender.send(result).ok();
+            })
+        };
+        Self {
+            shared,
+            receiver,
+            thread: Some(thread),
+            result: None,
+        }
+    }
+
+    /// The fraction of the document which has been encoded, between 0 and 1.
+    ///
+    /// This is 0 while the document is being loaded and parsed, and 1 once it is finished. The descendants
+    /// of nodes which aren't drawn, e.g. because they are outside of the
+    /// [viewport](RenderOptions::viewport), are skipped, so it can jump ahead.
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.shared.progress.load(Ordering::Relaxed))
+    }
+
+    /// Stop encoding the document as soon as possible, after which the task finishes with
+    /// [`Error::Cancelled`], unless it finished already.
+    ///
+    /// Loading and parsing can't be interrupted, so this takes effect once the document is
+    /// parsed.
+    pub fn cancel(&self) {
+        self.shared.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the result is ready, so that [`wait`](Self::wait) won't block.
+    pub fn is_finished(&mut self) -> bool {
+        self.poll(Some(Duration::ZERO));
+        self.result.is_some()
+    }
+
+    /// Take the result if it is ready, without blocking.
+    ///
+    /// This returns `None` once the result has been taken.
+    pub fn try_take(&mut self) -> Option<Result<RenderedSvg, Error>> {
+        self.poll(Some(Duration::ZERO));
+        self.result.take()
+    }
+
+    /// Take the result, waiting up to `timeout` for it to be ready.
+    ///
+    /// This returns `None` once the result has been taken.
+    pub fn take_timeout(&mut self, timeout: Duration) -> Option<Result<RenderedSvg, Error>> {
+        self.poll(Some(timeout));
+        self.result.take()
+    }
+
+    /// Wait for the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if rendering panicked on the background thread.
+    pub fn wait(mut self) -> Result<RenderedSvg, Error> {
+        self.poll(None);
+        self.result
+            .take()
+            .expect("the background thread always sends a result")
+    }
+
+    /// Receive the result, if it hasn't been received yet, waiting up to `timeout` or forever.
+    fn poll(&mut self, timeout: Option<Duration>) {
+        if self.result.is_some() || self.thread.is_none() {
+            return;
+        }
+        let received = match timeout {
+            Some(Duration::ZERO) => self.receiver.try_recv().map_err(|error| match error {
+                TryRecvError::Empty => RecvTimeoutError::Timeout,
+                TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+            }),
+            Some(timeout) => self.receiver.recv_timeout(timeout),
+            None => self
+                .receiver
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(result) => {
+                self.result = Some(result);
+                self.thread = None;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                if let Some(Err(panic)) = self.thread.take().map(JoinHandle::join) {
+                    std::panic::resume_unwind(panic);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for RenderTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl std::fmt::Debug for RenderTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderTask")
+            .field("progress", &self.progress())
+            .field("cancelled", &self.shared.cancelled.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parse and render a document on the background thread.
+fn run(
+    svg: &[u8],
+    opt: &usvg::Options<'_>,
+    options: &RenderOptions,
+    shared: &Shared,
+) -> Result<RenderedSvg, Error> {
+    let parsed = crate::parse_bytes(svg, opt)?;
+    if shared.cancelled.load(Ordering::Relaxed) {
+        return Err(Error::Cancelled);
+    }
+    let total = count_nodes(parsed.tree.root());
+    let mut render = IncrementalRender::new(&parsed.tree, options);
+    while !render.resume(Budget::nodes(NODES_PER_STEP))? {
+        if shared.cancelled.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled);
+        }
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "the progress doesn't need to be precise"
+        )]
+        let progress = render.nodes() as f32 / total as f32;
+        shared
+            .progress
+            .store(progress.min(1.0).to_bits(), Ordering::Relaxed);
+    }
+    let mut rendered = render.finish()?;
+    rendered.stats.parse_time = parsed.parse_time;
+    shared.progress.store(1.0_f32.to_bits(), Ordering::Relaxed);
+    Ok(rendered)
+}
+
+/// The number of nodes drawn for the descendants of `root`, including those of nested SVG
+/// images and text.
+fn count_nodes(root: &usvg::Group) -> usize {
+    let mut count = 0;
+    // The children of the groups being counted which haven't been counted yet.
+    let mut stack = vec![root.children().iter()];
+    while let Some(children) = stack.last_mut() {
+        let Some(node) = children.next() else {
+            stack.pop();
+            continue;
+        };
+        count += 1;
+        match node {
+            usvg::Node::Group(g) => stack.push(g.children().iter()),
+            usvg::Node::Image(image) => {
+                if let usvg::ImageKind::SVG(svg) = image.kind() {
+                    stack.push(svg.root().children().iter());
+                }
+            }
+            usvg::Node::Text(text) => stack.push(text.flattened().children().iter()),
+            usvg::Node::Path(_) => {}
+        }
+    }
+    count
+}
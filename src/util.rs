@@ -4,7 +4,7 @@
 use vello::Scene;
 use vello::kurbo::{Affine, BezPath, Point, Rect, Stroke};
 use vello::peniko::color::{DynamicColor, palette};
-use vello::peniko::{Brush, Color, Fill};
+use vello::peniko::{Brush, Color, Compose, Fill, Mix};
 
 #[cfg(feature = "image")]
 use vello::peniko::{Blob, ImageBrush};
@@ -21,6 +21,15 @@ pub fn to_affine(ts: &usvg::Transform) -> Affine {
     Affine::new([sx, ky, kx, sy, tx, ty].map(|&x| f64::from(x)))
 }
 
+pub fn to_rect(rect: &usvg::Rect) -> Rect {
+    Rect::new(
+        rect.left() as f64,
+        rect.top() as f64,
+        rect.right() as f64,
+        rect.bottom() as f64,
+    )
+}
+
 pub fn to_stroke(stroke: &usvg::Stroke) -> Stroke {
     let mut conv_stroke = Stroke::new(stroke.width().get() as f64)
         .with_caps(match stroke.linecap() {
@@ -111,14 +120,24 @@ pub fn into_image(image: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>) -> ImageB
 }
 
 pub fn to_brush(paint: &usvg::Paint, opacity: usvg::Opacity) -> Option<(Brush, Affine)> {
+    to_brush_with(paint, opacity, |color| color)
+}
+
+/// Like [`to_brush`], but with each color (including those of gradient stops) passed through
+/// `map_color` first, see [`PaintOverride`](crate::PaintOverride).
+pub fn to_brush_with(
+    paint: &usvg::Paint,
+    opacity: usvg::Opacity,
+    map_color: impl Fn(Color) -> Color,
+) -> Option<(Brush, Affine)> {
     match paint {
         usvg::Paint::Color(color) => Some((
-            Brush::Solid(Color::from_rgba8(
+            Brush::Solid(map_color(Color::from_rgba8(
                 color.red,
                 color.green,
                 color.blue,
                 opacity.to_u8(),
-            )),
+            ))),
             Affine::IDENTITY,
         )),
         usvg::Paint::LinearGradient(gr) => {
@@ -127,12 +146,12 @@ pub fn to_brush(paint: &usvg::Paint, opacity: usvg::Opacity) -> Option<(Brush, A
                 .iter()
                 .map(|stop| vello::peniko::ColorStop {
                     offset: stop.offset().get(),
-                    color: DynamicColor::from_alpha_color(Color::from_rgba8(
+                    color: DynamicColor::from_alpha_color(map_color(Color::from_rgba8(
                         stop.color().red,
                         stop.color().green,
                         stop.color().blue,
                         (stop.opacity() * opacity).to_u8(),
-                    )),
+                    ))),
                 })
                 .collect();
             let start = Point::new(gr.x1() as f64, gr.y1() as f64);
@@ -157,12 +176,12 @@ pub fn to_brush(paint: &usvg::Paint, opacity: usvg::Opacity) -> Option<(Brush, A
                 .iter()
                 .map(|stop| vello::peniko::ColorStop {
                     offset: stop.offset().get(),
-                    color: DynamicColor::from_alpha_color(Color::from_rgba8(
+                    color: DynamicColor::from_alpha_color(map_color(Color::from_rgba8(
                         stop.color().red,
                         stop.color().green,
                         stop.color().blue,
                         (stop.opacity() * opacity).to_u8(),
-                    )),
+                    ))),
                 })
                 .collect();
 
@@ -193,16 +212,31 @@ pub fn to_brush(paint: &usvg::Paint, opacity: usvg::Opacity) -> Option<(Brush, A
     }
 }
 
+/// Draw whatever `draw` draws inside of `bounds` in a single `color`, keeping its shape and
+/// transparency.
+pub(crate) fn draw_tinted(
+    scene: &mut Scene,
+    transform: Affine,
+    bounds: &Rect,
+    color: Color,
+    draw: impl FnOnce(&mut Scene),
+) {
+    scene.push_layer(Fill::NonZero, Mix::Normal, 1.0, transform, bounds);
+    draw(scene);
+    scene.push_layer(Fill::NonZero, Compose::SrcIn, 1.0, transform, bounds);
+    scene.fill(Fill::NonZero, transform, color, None, bounds);
+    scene.pop_layer();
+    scene.pop_layer();
+}
+
 /// Error handler function for [`super::append_tree_with`] which draws a transparent red box
 /// instead of unsupported SVG features
+///
+/// This doesn't know the transform of the node, so the box is drawn in the coordinate space of
+/// the node rather than of the scene. Prefer [`DefaultErrorHandler`](crate::DefaultErrorHandler),
+/// which also handles transformed nodes.
 pub fn default_error_handler(scene: &mut Scene, node: &usvg::Node) {
-    let bb = node.bounding_box();
-    let rect = Rect {
-        x0: bb.left() as f64,
-        y0: bb.top() as f64,
-        x1: bb.right() as f64,
-        y1: bb.bottom() as f64,
-    };
+    let rect = to_rect(&node.bounding_box());
     scene.fill(
         Fill::NonZero,
         Affine::IDENTITY,
@@ -216,17 +250,43 @@ pub fn default_error_handler(scene: &mut Scene, node: &usvg::Node) {
 pub fn decode_raw_raster_image(
     img: &usvg::ImageKind,
 ) -> Result<image::RgbaImage, image::ImageError> {
+    let (data, format) = raster_image_data(img)?;
+    let dyn_image = image::load_from_memory_with_format(data, format)?;
+    Ok(dyn_image.into_rgba8())
+}
+
+/// Read the width and height of a raster image, without decoding it.
+#[cfg(feature = "image")]
+pub(crate) fn raster_image_dimensions(
+    img: &usvg::ImageKind,
+) -> Result<(u32, u32), image::ImageError> {
+    let (data, format) = raster_image_data(img)?;
+    image::ImageReader::with_format(std::io::Cursor::new(data), format).into_dimensions()
+}
+
+#[cfg(feature = "image")]
+fn raster_image_data(
+    img: &usvg::ImageKind,
+) -> Result<(&[u8], image::ImageFormat), image::ImageError> {
     // All `image::ImageFormat` variants exist even if the feature in the image crate is disabled,
     // but `image::load_from_memory_with_format` will fail with an Unsupported error if the
     // image crate feature flag is disabled. So we don't need any of our own feature handling here.
-    let (data, format) = match img {
-        usvg::ImageKind::JPEG(data) => (data, image::ImageFormat::Jpeg),
-        usvg::ImageKind::PNG(data) => (data, image::ImageFormat::Png),
-        usvg::ImageKind::GIF(data) => (data, image::ImageFormat::Gif),
-        usvg::ImageKind::WEBP(data) => (data, image::ImageFormat::WebP),
-        usvg::ImageKind::SVG(_) => unreachable!(),
-    };
+    match img {
+        usvg::ImageKind::JPEG(data) => Ok((data, image::ImageFormat::Jpeg)),
+        usvg::ImageKind::PNG(data) => Ok((data, image::ImageFormat::Png)),
+        usvg::ImageKind::GIF(data) => Ok((data, image::ImageFormat::Gif)),
+        usvg::ImageKind::WEBP(data) => Ok((data, image::ImageFormat::WebP)),
+        usvg::ImageKind::SVG(_) => Err(image::ImageError::Unsupported(
+            image::error::ImageFormatHint::Name("SVG".to_owned()).into(),
+        )),
+    }
+}
 
-    let dyn_image = image::load_from_memory_with_format(data, format)?;
-    Ok(dyn_image.into_rgba8())
+/// The qualified name of an element, from its source text.
+pub(crate) fn start_tag_name(element: &str) -> &str {
+    let name = element.strip_prefix('<').unwrap_or(element);
+    let end = name
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(name.len());
+    &name[..end]
 }
@@ -0,0 +1,81 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::append_tree_composited")]
+#[cfg(test)]
+pub mod append_tree_composited_test {
+    use vello::Scene;
+    use vello::kurbo::{Affine, Rect};
+    use vello::peniko::{Fill, Mix};
+    use vello_svg::{DefaultErrorHandler, usvg};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <rect width="10" height="10" fill="blue"/>
+    </svg>"#;
+
+    fn tree() -> usvg::Tree {
+        usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap()
+    }
+
+    #[test]
+    fn wraps_content_in_one_layer() {
+        let tree = tree();
+        let mut plain = Scene::new();
+        vello_svg::append_tree(&mut plain, &tree);
+
+        let mut composited = Scene::new();
+        vello_svg::append_tree_composited(
+            &mut composited,
+            &tree,
+            Affine::translate((5.0, 5.0)),
+            0.5,
+            Mix::Multiply,
+            None::<&Rect>,
+            &mut DefaultErrorHandler,
+        )
+        .unwrap();
+
+        let (plain, composited) = (plain.encoding(), composited.encoding());
+        assert_eq!(
+            composited.n_clips,
+            plain.n_clips + 2,
+            "expected exactly one extra layer (begin and end)"
+        );
+        assert_eq!(composited.n_open_clips, 0, "all layers should be popped");
+    }
+
+    #[test]
+    fn explicit_clip_shape() {
+        let tree = tree();
+        let transform = Affine::translate((5.0, 5.0));
+        let clip = Rect::new(0.0, 0.0, 5.0, 5.0);
+        let mut scene = Scene::new();
+        vello_svg::append_tree_composited(
+            &mut scene,
+            &tree,
+            transform,
+            1.0,
+            Mix::Normal,
+            Some(&clip),
+            &mut DefaultErrorHandler,
+        )
+        .unwrap();
+
+        // The same layer, pushed by hand around the document drawn with the same transform.
+        let mut content = Scene::new();
+        vello_svg::append_tree(&mut content, &tree);
+        let mut expected = Scene::new();
+        expected.push_layer(Fill::NonZero, Mix::Normal, 1.0, transform, &clip);
+        expected.append(&content, Some(transform));
+        expected.pop_layer();
+
+        let (a, b) = (scene.encoding(), expected.encoding());
+        assert_eq!(a.path_data, b.path_data, "the clip shape is encoded");
+        assert!(
+            !a.transforms.is_empty() && a.transforms.iter().all(|t| *t == b.transforms[0]),
+            "the clip and the content are drawn with the transform"
+        );
+        assert!(a.draw_tags == b.draw_tags, "draw tags differ");
+        assert_eq!(a.n_open_clips, 0, "all layers should be popped");
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::CurrentColorSvg")]
+#[cfg(test)]
+pub mod current_color_test {
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::Affine;
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::{CurrentColorSvg, usvg};
+
+    fn icon(color: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                <linearGradient id="gradient">
+                    <stop offset="0" stop-color="{color}"/>
+                    <stop offset="1" stop-color="red" stop-opacity="0.5"/>
+                </linearGradient>
+                <rect width="5" height="5" fill="{color}" stroke="{color}" stroke-opacity="0.5"/>
+                <rect x="10" width="5" height="5" fill="url(#gradient)"/>
+                <g color="green">
+                    <rect y="6" width="2" height="2" fill="currentColor"/>
+                </g>
+            </svg>"#
+        )
+    }
+
+    /// Assert that two scenes draw the same shapes with the same colors.
+    fn assert_same(a: &Scene, b: &Scene, message: &str) {
+        let (a, b) = (a.encoding(), b.encoding());
+        assert!(a.draw_tags == b.draw_tags, "{message}: draws differ");
+        assert_eq!(a.draw_data, b.draw_data, "{message}: draw data differs");
+        assert_eq!(
+            a.resources.color_stops, b.resources.color_stops,
+            "{message}: gradient stops differ"
+        );
+    }
+
+    #[test]
+    fn default_is_black() {
+        let svg = CurrentColorSvg::new(&icon("currentColor")).unwrap();
+        assert_eq!(svg.color(), palette::css::BLACK, "the default color");
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("black")).unwrap().scene,
+            "currentColor is black",
+        );
+    }
+
+    #[test]
+    fn set_color() {
+        let mut svg = CurrentColorSvg::new(&icon("currentColor")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("blue")).unwrap().scene,
+            "fills, strokes and gradient stops are blue, keeping their opacity",
+        );
+
+        let mut scene = Scene::new();
+        svg.append_to(&mut scene, Affine::IDENTITY, palette::css::YELLOW);
+        assert_same(
+            &scene,
+            &vello_svg::render(&icon("yellow")).unwrap().scene,
+            "the color can be changed again",
+        );
+    }
+
+    #[test]
+    fn other_colors_are_unchanged() {
+        let mut svg = CurrentColorSvg::new(&icon("purple")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("purple")).unwrap().scene,
+            "the document doesn't use currentColor outside of the green group",
+        );
+    }
+
+    #[test]
+    fn nested_svg() {
+        let icon = |color: &str| {
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                    <g color="green">
+                        <svg width="10" height="10"><rect width="5" height="5" fill="currentColor"/></svg>
+                    </g>
+                    <svg x="10" width="10" height="10"><rect width="5" height="5" fill="{color}"/></svg>
+                </svg>"#
+            )
+        };
+        let mut svg = CurrentColorSvg::new(&icon("currentColor")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("blue")).unwrap().scene,
+            "nested documents inherit the color of their parent",
+        );
+    }
+
+    #[test]
+    fn single_stop_gradient() {
+        let icon = |color: &str| {
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                    <linearGradient id="gradient"><stop stop-color="{color}" stop-opacity="0.5"/></linearGradient>
+                    <radialGradient id="radial">
+                        <stop offset="0.5" stop-color="{color}"/>
+                        <stop offset="0.5" stop-color="{color}"/>
+                    </radialGradient>
+                    <rect width="5" height="10" fill="url(#gradient)"/>
+                    <rect x="5" width="5" height="10" fill="url(#radial)"/>
+                </svg>"#
+            )
+        };
+        let mut svg = CurrentColorSvg::new(&icon("currentColor")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("blue")).unwrap().scene,
+            "gradients with a single color are drawn in the current color",
+        );
+    }
+
+    #[test]
+    fn root_color() {
+        let icon = |root: &str, color: &str| {
+            format!(
+                r#"<svg:svg xmlns:svg="http://www.w3.org/2000/svg" width="10" height="10"{root}>
+                    <svg:rect width="5" height="5" fill="{color}"/>
+                </svg:svg>"#
+            )
+        };
+        let mut svg = CurrentColorSvg::new(&icon(r#" color="red""#, "currentColor")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("", "blue")).unwrap().scene,
+            "the color of the root element is replaced",
+        );
+    }
+
+    #[test]
+    fn unsupported_nodes() {
+        let icon = |color: &str| {
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                    <pattern id="pattern" width="2" height="2" patternUnits="userSpaceOnUse">
+                        <rect width="1" height="1" fill="{color}"/>
+                    </pattern>
+                    <rect width="5" height="5" fill="url(#pattern)"/>
+                    <rect x="10" width="5" height="5" fill="{color}"/>
+                </svg>"#
+            )
+        };
+        let mut calls = 0;
+        CurrentColorSvg::with_handler(
+            &icon("currentColor"),
+            &usvg::Options::default(),
+            &mut |_: &mut Scene, _: &usvg::Node| calls += 1,
+        )
+        .unwrap();
+        assert_eq!(calls, 1, "the error handler is called once for each node");
+
+        let mut svg = CurrentColorSvg::new(&icon("currentColor")).unwrap();
+        svg.set_color(palette::css::BLUE);
+        assert_same(
+            &svg.rendered().scene,
+            &vello_svg::render(&icon("blue")).unwrap().scene,
+            "what the error handler draws is kept",
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::DebugOverlay")]
+#[cfg(test)]
+pub mod debug_overlay_test {
+    use std::cell::RefCell;
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::{Affine, Rect};
+    use vello_svg::vello::peniko::Fill;
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::{DebugOverlay, DefaultErrorHandler, RenderOptions, usvg};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+        <clipPath id="c"><circle cx="10" cy="10" r="8"/></clipPath>
+        <g id="clipped" clip-path="url(#c)">
+            <rect id="r1" width="20" height="20" fill="red"/>
+        </g>
+    </svg>"#;
+
+    #[test]
+    fn overlay_adds_draws_on_top() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let plain = vello_svg::render_tree(&tree);
+        let options = RenderOptions {
+            debug: Some(DebugOverlay::default()),
+            ..Default::default()
+        };
+        let debug =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+
+        let plain_encoding = plain.scene.encoding();
+        let debug_encoding = debug.scene.encoding();
+        assert!(
+            debug_encoding.draw_tags.len() > plain_encoding.draw_tags.len(),
+            "the overlay draws outlines"
+        );
+        assert!(
+            debug_encoding.draw_tags[..plain_encoding.draw_tags.len()]
+                == plain_encoding.draw_tags[..],
+            "the document is drawn first"
+        );
+        assert_eq!(debug_encoding.n_open_clips, 0, "layers are balanced");
+    }
+
+    #[test]
+    fn disabled_overlay_draws_nothing() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let plain = vello_svg::render_tree(&tree);
+        let mut overlay = DebugOverlay::default();
+        overlay.bounds = false;
+        overlay.clip_paths = false;
+        overlay.unsupported = false;
+        let options = RenderOptions {
+            debug: Some(overlay),
+            ..Default::default()
+        };
+        let debug =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+        assert!(
+            debug.scene.encoding().draw_tags == plain.scene.encoding().draw_tags,
+            "nothing is drawn when every part of the overlay is disabled"
+        );
+    }
+
+    thread_local! {
+        static LABELS: RefCell<Vec<(String, Rect)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn label(scene: &mut Scene, id: &str, bounds: Rect) {
+        LABELS.with_borrow_mut(|labels| labels.push((id.to_owned(), bounds)));
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            palette::css::BLACK,
+            None,
+            &Rect::from_origin_size(bounds.origin(), (4.0, 2.0)),
+        );
+    }
+
+    #[test]
+    fn ids() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let mut overlay = DebugOverlay::default();
+        overlay.ids = Some(label);
+        let options = RenderOptions {
+            debug: Some(overlay),
+            origin: Some((5.0, 0.0).into()),
+            ..Default::default()
+        };
+        let debug =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+        let bounds = Rect::new(-5.0, 0.0, 15.0, 20.0);
+        assert_eq!(
+            LABELS.take(),
+            [("clipped".to_owned(), bounds), ("r1".to_owned(), bounds)],
+            "each node with an id is labeled with its bounds in the scene"
+        );
+
+        overlay.ids = None;
+        let options = RenderOptions {
+            debug: Some(overlay),
+            ..options
+        };
+        let unlabeled =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+        let (labeled, unlabeled) = (debug.scene.encoding(), unlabeled.scene.encoding());
+        assert_eq!(
+            labeled.draw_tags.len(),
+            unlabeled.draw_tags.len() + 2,
+            "the labels are drawn"
+        );
+        assert!(
+            labeled.draw_tags[..unlabeled.draw_tags.len()] == unlabeled.draw_tags[..],
+            "the labels are drawn on top"
+        );
+    }
+}
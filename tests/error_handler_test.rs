@@ -0,0 +1,87 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::ErrorHandler")]
+#[cfg(test)]
+pub mod error_handler_test {
+    use vello::Scene;
+    use vello::kurbo::Affine;
+    use vello_svg::{Error, ErrorHandler, Unsupported, UnsupportedNode, usvg};
+
+    #[derive(Default)]
+    struct Recorder(Vec<(Unsupported, String, Vec<String>, Affine)>);
+
+    impl ErrorHandler for Recorder {
+        fn on_unsupported(
+            &mut self,
+            _scene: &mut Scene,
+            unsupported: &UnsupportedNode<'_>,
+        ) -> Result<(), Error> {
+            self.0.push((
+                unsupported.reason,
+                unsupported.node.id().to_owned(),
+                unsupported
+                    .ancestors
+                    .iter()
+                    .map(|group| group.id().to_owned())
+                    .collect(),
+                unsupported.transform,
+            ));
+            Ok(())
+        }
+    }
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <pattern id="p" width="2" height="2" patternUnits="userSpaceOnUse">
+            <rect width="1" height="1"/>
+        </pattern>
+        <filter id="f"><feGaussianBlur stdDeviation="1"/></filter>
+        <g id="outer" transform="translate(5 5)">
+            <rect id="patterned" width="10" height="10" fill="url(#p)"/>
+            <g id="filtered" filter="url(#f)"><rect width="1" height="1"/></g>
+        </g>
+    </svg>"#;
+
+    #[test]
+    fn reasons_ancestors_and_transforms() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let mut recorder = Recorder::default();
+        vello_svg::append_tree_with_handler(&mut Scene::new(), &tree, &mut recorder).unwrap();
+
+        let [
+            (pattern, pattern_id, pattern_ancestors, pattern_transform),
+            (filter, filter_id, ..),
+        ] = recorder.0.as_slice()
+        else {
+            panic!("expected two unsupported nodes, got {:?}", recorder.0);
+        };
+        assert_eq!(*pattern, Unsupported::PatternPaint, "wrong reason");
+        assert_eq!(pattern_id, "patterned", "wrong node");
+        assert_eq!(
+            pattern_ancestors.last().map(String::as_str),
+            Some("outer"),
+            "the parent group should be the last ancestor"
+        );
+        assert_eq!(
+            *pattern_transform,
+            Affine::translate((5.0, 5.0)),
+            "the transform should include the parent group"
+        );
+        assert_eq!(*filter, Unsupported::Filter, "wrong reason");
+        assert_eq!(filter_id, "filtered", "wrong node");
+    }
+
+    #[test]
+    fn closures_only_see_omitted_nodes() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let mut ids = Vec::new();
+        vello_svg::append_tree_with(
+            &mut Scene::new(),
+            &tree,
+            &mut |_: &mut Scene, node: &usvg::Node| {
+                ids.push(node.id().to_owned());
+            },
+        );
+        assert_eq!(ids, ["patterned"], "the filter should not be reported");
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::hit_test")]
+#[cfg(test)]
+pub mod hit_test_test {
+    use vello_svg::vello::kurbo::{Affine, Point};
+    use vello_svg::{HitPart, usvg};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <clipPath id="left"><rect width="50" height="100"/></clipPath>
+        <rect id="background" width="100" height="100" fill="white"/>
+        <g id="outer" transform="translate(10 10)">
+            <path id="ring" fill-rule="evenodd" d="M0 0 H40 V40 H0 Z M10 10 H30 V30 H10 Z"/>
+        </g>
+        <g id="clipped" clip-path="url(#left)">
+            <rect id="wide" x="40" y="60" width="20" height="20"/>
+        </g>
+        <line id="line" x1="70" y1="10" x2="90" y2="10" stroke="black" stroke-width="4"/>
+        <rect id="hidden" width="100" height="100" visibility="hidden"/>
+    </svg>"#;
+
+    fn hit(point: (f64, f64)) -> Option<(String, HitPart)> {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        vello_svg::hit_test(&tree, Point::new(point.0, point.1), Affine::IDENTITY)
+            .map(|hit| (hit.node.id().to_owned(), hit.part))
+    }
+
+    fn id(point: (f64, f64)) -> Option<String> {
+        hit(point).map(|(id, _)| id)
+    }
+
+    #[test]
+    fn fill_rule() {
+        assert_eq!(id((15.0, 15.0)).as_deref(), Some("ring"), "inside the ring");
+        assert_eq!(
+            id((30.0, 30.0)).as_deref(),
+            Some("background"),
+            "inside the hole of the ring"
+        );
+    }
+
+    #[test]
+    fn clip_path() {
+        assert_eq!(id((45.0, 70.0)).as_deref(), Some("wide"), "inside the clip");
+        assert_eq!(
+            id((55.0, 70.0)).as_deref(),
+            Some("background"),
+            "outside of the clip"
+        );
+    }
+
+    #[test]
+    fn stroke() {
+        assert_eq!(
+            hit((80.0, 11.0)),
+            Some(("line".to_owned(), HitPart::Stroke)),
+            "inside the stroke"
+        );
+        assert_eq!(
+            id((80.0, 13.0)).as_deref(),
+            Some("background"),
+            "outside of the stroke"
+        );
+    }
+
+    #[test]
+    fn ancestors_and_transform() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let hit = vello_svg::hit_test(&tree, Point::new(30.0, 30.0), Affine::scale(2.0)).unwrap();
+        assert_eq!(hit.node.id(), "ring", "the point is scaled down");
+        let ancestors: Vec<_> = hit.ancestors.iter().map(|group| group.id()).collect();
+        assert_eq!(
+            ancestors,
+            ["", "outer"],
+            "the root group and the outer group"
+        );
+
+        assert!(
+            vello_svg::hit_test(&tree, Point::new(150.0, 50.0), Affine::IDENTITY).is_none(),
+            "outside of the document"
+        );
+    }
+
+    fn hit_in(svg: &str, opt: &usvg::Options<'_>, point: (f64, f64)) -> Option<(String, HitPart)> {
+        let tree = usvg::Tree::from_str(svg, opt).unwrap();
+        vello_svg::hit_test(&tree, Point::new(point.0, point.1), Affine::IDENTITY)
+            .map(|hit| (hit.node.id().to_owned(), hit.part))
+    }
+
+    #[test]
+    fn transformed_group() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect id="background" width="100" height="100" fill="white"/>
+            <g transform="translate(50 50)">
+                <g id="scaled" transform="scale(2) rotate(45)">
+                    <rect id="square" x="-5" y="-5" width="10" height="10"/>
+                </g>
+            </g>
+        </svg>"#;
+        let opt = usvg::Options::default();
+        assert_eq!(
+            hit_in(svg, &opt, (50.0, 63.0)),
+            Some(("square".to_owned(), HitPart::Fill)),
+            "inside the rotated corner"
+        );
+        assert_eq!(
+            hit_in(svg, &opt, (60.0, 60.0)).map(|(id, _)| id).as_deref(),
+            Some("background"),
+            "inside the bounds of the square before it is rotated"
+        );
+    }
+
+    #[test]
+    fn transformed_clip_path() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <clipPath id="right" transform="translate(50 0)">
+                <rect width="50" height="100"/>
+            </clipPath>
+            <rect id="background" width="100" height="100" fill="white"/>
+            <g clip-path="url(#right)" transform="translate(0 10)">
+                <rect id="clipped" width="100" height="80"/>
+            </g>
+        </svg>"#;
+        let opt = usvg::Options::default();
+        assert_eq!(
+            hit_in(svg, &opt, (75.0, 50.0)).map(|(id, _)| id).as_deref(),
+            Some("clipped"),
+            "inside the translated clip"
+        );
+        assert_eq!(
+            hit_in(svg, &opt, (25.0, 50.0)).map(|(id, _)| id).as_deref(),
+            Some("background"),
+            "where the clip would be without its transform"
+        );
+    }
+
+    #[test]
+    fn visibility_and_opacity() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect id="background" width="100" height="100" fill="white"/>
+            <rect id="transparent" width="50" height="100" opacity="0"/>
+            <g visibility="hidden">
+                <rect id="hidden" width="100" height="100"/>
+                <rect id="visible" x="50" width="50" height="50" visibility="visible"/>
+            </g>
+            <rect id="none" width="100" height="100" display="none"/>
+        </svg>"#;
+        let opt = usvg::Options::default();
+        assert_eq!(
+            hit_in(svg, &opt, (25.0, 50.0)).map(|(id, _)| id).as_deref(),
+            Some("transparent"),
+            "opacity is not taken into account"
+        );
+        assert_eq!(
+            hit_in(svg, &opt, (75.0, 25.0)).map(|(id, _)| id).as_deref(),
+            Some("visible"),
+            "a visible child of a hidden group"
+        );
+        assert_eq!(
+            hit_in(svg, &opt, (75.0, 75.0)).map(|(id, _)| id).as_deref(),
+            Some("background"),
+            "hidden and undisplayed nodes are skipped"
+        );
+    }
+
+    #[test]
+    fn text() {
+        let mut opt = usvg::Options::default();
+        opt.fontdb_mut().load_system_fonts();
+        // The middle of an "I" is inside of its stem in sans-serif fonts.
+        let families: Vec<_> = opt
+            .fontdb
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect();
+        let Some(family) = families
+            .iter()
+            .find(|name| name.contains("Sans") && !name.contains("Mono"))
+            .or(families.first())
+        else {
+            // Text can't be laid out without a font.
+            return;
+        };
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <rect id="background" width="100" height="100" fill="white"/>
+                <g id="label" transform="translate(10 0)">
+                    <text id="text" y="80" font-family="{family}" font-size="80">I</text>
+                </g>
+            </svg>"#
+        );
+        let tree = usvg::Tree::from_str(&svg, &opt).unwrap();
+        let usvg::Node::Text(text) = tree.node_by_id("text").unwrap() else {
+            panic!("expected a text node");
+        };
+        // The flattened group is positioned relative to the text.
+        let bounds = text
+            .flattened()
+            .abs_bounding_box()
+            .transform(text.abs_transform())
+            .unwrap();
+        let center = Point::new(
+            f64::from(bounds.left() + bounds.right()) / 2.0,
+            f64::from(bounds.top() + bounds.bottom()) / 2.0,
+        );
+
+        let hit = vello_svg::hit_test(&tree, center, Affine::IDENTITY).unwrap();
+        assert_eq!(hit.node.id(), "text", "the text node is reported");
+        assert_eq!(hit.part, HitPart::Fill, "the fill of the glyph");
+        let ancestors: Vec<_> = hit.ancestors.iter().map(|group| group.id()).collect();
+        assert_eq!(ancestors, ["", "label"], "the groups containing the text");
+
+        assert_eq!(
+            hit_in(&svg, &opt, (f64::from(bounds.right()) + 5.0, center.y))
+                .map(|(id, _)| id)
+                .as_deref(),
+            Some("background"),
+            "beside the glyph"
+        );
+    }
+}
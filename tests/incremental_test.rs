@@ -0,0 +1,148 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::IncrementalRender")]
+#[cfg(test)]
+pub mod incremental_test {
+    use std::fmt::Write as _;
+    use std::time::Duration;
+    use vello_svg::vello::Scene;
+    use vello_svg::{
+        Budget, DefaultErrorHandler, IncrementalRender, Limits, RenderOptions, RenderedSvg, usvg,
+    };
+
+    /// A document with 10 nested groups of 10 shapes each, some of them in layers.
+    fn document() -> usvg::Tree {
+        let mut svg = String::from(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <clipPath id="clip"><circle cx="50" cy="50" r="40"/></clipPath>"#,
+        );
+        for i in 0..10 {
+            match i % 3 {
+                0 => svg.push_str(r#"<g clip-path="url(#clip)">"#),
+                1 => write!(svg, r#"<g opacity="0.5" transform="translate({i} 0)">"#).unwrap(),
+                _ => svg.push_str("<g>"),
+            }
+            for j in 0..10 {
+                write!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="5" height="5" stroke="black"/>"#,
+                    j * 10,
+                    i * 10
+                )
+                .unwrap();
+            }
+            svg.push_str("</g>");
+        }
+        svg.push_str("</svg>");
+        usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap()
+    }
+
+    fn assert_identical(a: &Scene, b: &Scene) {
+        let (a, b) = (a.encoding(), b.encoding());
+        assert!(a.path_tags == b.path_tags, "path tags differ");
+        assert_eq!(a.path_data, b.path_data, "path data differs");
+        assert!(a.draw_tags == b.draw_tags, "draw tags differ");
+        assert_eq!(a.draw_data, b.draw_data, "draw data differs");
+        assert_eq!(a.transforms, b.transforms, "transforms differ");
+        assert_eq!(a.n_paths, b.n_paths, "path counts differ");
+        assert_eq!(a.n_clips, b.n_clips, "clip counts differ");
+    }
+
+    fn assert_same_as_serial(tree: &usvg::Tree, incremental: RenderedSvg) {
+        let serial = vello_svg::render_tree(tree);
+        assert_identical(&incremental.scene, &serial.scene);
+        assert_eq!(incremental.report, serial.report, "the reports differ");
+        let (mut incremental_stats, mut serial_stats) = (incremental.stats, serial.stats);
+        incremental_stats.encode_time = None;
+        serial_stats.encode_time = None;
+        assert_eq!(incremental_stats, serial_stats, "the statistics differ");
+    }
+
+    #[test]
+    fn node_budget() {
+        let tree = document();
+        let mut render = IncrementalRender::new(&tree, &RenderOptions::default());
+        let mut steps = 1;
+        while !render.resume(Budget::nodes(7)).unwrap() {
+            steps += 1;
+        }
+        assert!(render.is_finished(), "the document was encoded");
+        assert!(
+            steps >= 110_usize.div_ceil(7),
+            "each step encodes at most 7 of the 110 nodes, took {steps} steps"
+        );
+        assert_same_as_serial(&tree, render.finish().unwrap());
+    }
+
+    #[test]
+    fn one_node_at_a_time() {
+        let tree = document();
+        let mut render = IncrementalRender::new(&tree, &RenderOptions::default());
+        let mut steps = 1;
+        while !render.resume(Budget::nodes(1)).unwrap() {
+            steps += 1;
+        }
+        assert_eq!(steps, 110, "one step for each node");
+        assert_same_as_serial(&tree, render.finish().unwrap());
+    }
+
+    #[test]
+    fn always_progresses() {
+        let tree = document();
+        let mut render = IncrementalRender::new(&tree, &RenderOptions::default());
+        for _ in 0..200 {
+            if render.resume(Budget::time(Duration::ZERO)).unwrap() {
+                break;
+            }
+        }
+        assert!(
+            render.is_finished(),
+            "each step encodes at least one node, even without time"
+        );
+    }
+
+    #[test]
+    fn finish_encodes_the_rest() {
+        let tree = document();
+        let mut render = IncrementalRender::new(&tree, &RenderOptions::default());
+        assert!(
+            !render.resume(Budget::nodes(50)).unwrap(),
+            "half of the document is encoded"
+        );
+        assert_same_as_serial(&tree, render.finish().unwrap());
+    }
+
+    #[test]
+    fn exceeded_limit() {
+        let tree = document();
+        let options = RenderOptions {
+            limits: Limits {
+                max_nodes: 50,
+                ..Limits::default()
+            },
+            ..Default::default()
+        };
+        let serial = vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+            .unwrap_err();
+        let mut render = IncrementalRender::new(&tree, &options);
+        let error = loop {
+            match render.resume(Budget::nodes(10)) {
+                Ok(finished) => assert!(!finished, "the limit is exceeded before the end"),
+                Err(error) => break error,
+            }
+        };
+        assert_eq!(
+            error.to_string(),
+            serial.to_string(),
+            "the same node exceeds the limit"
+        );
+        assert!(render.is_finished(), "nothing more is encoded");
+        let partial = render.finish().unwrap();
+        assert_eq!(
+            partial.scene.encoding().n_open_clips,
+            0,
+            "the layers are balanced"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(
+    missing_docs,
+    reason = "tests for vello_svg::RenderOptions::instancing"
+)]
+#[cfg(test)]
+pub mod instancing_test {
+    use std::fmt::Write as _;
+    use vello_svg::vello::kurbo::Rect;
+    use vello_svg::{DefaultErrorHandler, Error, Limits, RenderOptions, RenderedSvg, usvg};
+
+    /// A map with 100 copies of a marker, the first 10 of which are red.
+    fn map() -> usvg::Tree {
+        let mut svg = String::from(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="100">
+                <defs>
+                    <g id="marker" opacity="0.5">
+                        <circle r="2" stroke="black"/>
+                        <path d="M-1 -1 L1 1 M-1 1 L1 -1" stroke="white"/>
+                    </g>
+                </defs>"#,
+        );
+        for i in 0..100 {
+            let fill = if i < 10 { "red" } else { "blue" };
+            write!(
+                svg,
+                r##"<use xlink:href="#marker" x="{}" y="{}" fill="{fill}"/>"##,
+                i % 10 * 10 + 5,
+                i / 10 * 10 + 5
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>");
+        usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap()
+    }
+
+    fn render(tree: &usvg::Tree, options: RenderOptions) -> Result<RenderedSvg, Error> {
+        vello_svg::render_tree_with_options(tree, &options, &mut DefaultErrorHandler)
+    }
+
+    fn instancing() -> RenderOptions {
+        RenderOptions {
+            instancing: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_scene() {
+        let tree = map();
+        let expected = render(&tree, RenderOptions::default()).unwrap();
+        let instanced = render(&tree, instancing()).unwrap();
+        let (a, b) = (instanced.scene.encoding(), expected.scene.encoding());
+        assert!(a.path_tags == b.path_tags, "path tags differ");
+        assert_eq!(a.path_data, b.path_data, "path data differs");
+        assert!(a.draw_tags == b.draw_tags, "draw tags differ");
+        assert_eq!(a.draw_data, b.draw_data, "draw data differs");
+        assert_eq!(a.n_paths, b.n_paths, "path counts differ");
+        assert_eq!(a.n_clips, b.n_clips, "clip counts differ");
+        assert_eq!(
+            instanced.stats.path_segments, expected.stats.path_segments,
+            "all path segments are counted"
+        );
+    }
+
+    #[test]
+    fn encodes_each_marker_once() {
+        let tree = map();
+        let expected = render(&tree, RenderOptions::default()).unwrap();
+        let instanced = render(&tree, instancing()).unwrap();
+        assert_eq!(
+            instanced.stats.instances, 98,
+            "the first red and the first blue markers are encoded"
+        );
+        assert_eq!(expected.stats.instances, 0, "instancing is off by default");
+        assert_eq!(
+            instanced.stats.paths,
+            expected.stats.paths / 50,
+            "the paths of two markers are drawn"
+        );
+    }
+
+    #[test]
+    fn culls_instances() {
+        let tree = map();
+        let instanced = render(
+            &tree,
+            RenderOptions {
+                viewport: Some(Rect::new(0.0, 0.0, 20.0, 20.0)),
+                ..instancing()
+            },
+        )
+        .unwrap();
+        assert_eq!(instanced.stats.culled_nodes, 96, "the markers outside");
+        assert_eq!(instanced.stats.instances, 2, "the markers inside");
+    }
+
+    #[test]
+    fn limits_count_instances() {
+        let tree = map();
+        let limits = Limits {
+            max_nodes: 200,
+            ..Limits::default()
+        };
+        let error = render(
+            &tree,
+            RenderOptions {
+                limits,
+                ..instancing()
+            },
+        )
+        .unwrap_err();
+        assert!(
+            matches!(error, Error::LimitExceeded { .. }),
+            "the content of instances counts towards the limit"
+        );
+    }
+}
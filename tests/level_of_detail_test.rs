@@ -0,0 +1,64 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::LevelOfDetail")]
+#[cfg(test)]
+pub mod level_of_detail_test {
+    use std::fmt::Write as _;
+    use vello_svg::{DefaultErrorHandler, LevelOfDetail, RenderOptions, RenderStats, usvg};
+
+    /// A large square with 100 short hatching lines on top.
+    fn drawing() -> String {
+        let mut svg = String::from(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="1000" height="1000">
+                <rect width="1000" height="1000" fill="white"/>
+                <g fill="none" stroke="black" stroke-width="0.1">"#,
+        );
+        for i in 0..100 {
+            let x = i * 10;
+            write!(svg, r#"<path d="M{x} 0 l4 4"/>"#).unwrap();
+        }
+        svg.push_str("</g></svg>");
+        svg
+    }
+
+    fn stats(level_of_detail: LevelOfDetail) -> RenderStats {
+        let tree = usvg::Tree::from_str(&drawing(), &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            level_of_detail: Some(level_of_detail),
+            ..Default::default()
+        };
+        vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+            .unwrap()
+            .stats
+    }
+
+    #[test]
+    fn thumbnail() {
+        let thumbnail = stats(LevelOfDetail::new(0.1));
+        assert_eq!(
+            thumbnail.small_nodes, 100,
+            "the hatching is too small to see"
+        );
+        assert_eq!(thumbnail.paths, 1, "only the large square is drawn");
+    }
+
+    #[test]
+    fn full_size() {
+        let full_size = stats(LevelOfDetail::new(1.0));
+        assert_eq!(full_size.small_nodes, 0, "everything is large enough");
+        assert_eq!(full_size.paths, 101, "the square and the hatching");
+    }
+
+    #[test]
+    fn min_size() {
+        let coarse = stats(LevelOfDetail {
+            scale: 1.0,
+            min_size: 10.0,
+        });
+        assert_eq!(
+            coarse.small_nodes, 100,
+            "the hatching is smaller than 10 pixels"
+        );
+    }
+}
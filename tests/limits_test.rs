@@ -0,0 +1,132 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::Limits")]
+#[cfg(test)]
+pub mod limits_test {
+    use vello_svg::{DefaultErrorHandler, Error, Limit, Limits, RenderOptions, usvg};
+
+    /// Render `svg` leniently with `limits`, returning the exceeded limit, if any.
+    fn exceeded(svg: &str, limits: Limits) -> Option<Limit> {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            limits,
+            ..Default::default()
+        };
+        let result = vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler);
+        match result {
+            Ok(rendered) => {
+                assert_eq!(
+                    rendered.report.exceeded_limit, None,
+                    "the report should agree with the result"
+                );
+                None
+            }
+            Err(Error::LimitExceeded { limit, .. }) => Some(limit),
+            Err(err) => panic!("unexpected error {err}"),
+        }
+    }
+
+    const RECTS: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <rect width="5" height="5"/>
+        <rect x="5" width="5" height="5" stroke="black"/>
+        <g><rect y="5" width="5" height="5"/></g>
+    </svg>"#;
+
+    #[test]
+    fn nodes() {
+        let limits = |max_nodes| Limits {
+            max_nodes,
+            ..Limits::default()
+        };
+        assert_eq!(exceeded(RECTS, limits(4)), None, "four nodes are allowed");
+        assert_eq!(exceeded(RECTS, limits(3)), Some(Limit::Nodes), "four nodes");
+    }
+
+    #[test]
+    fn path_segments() {
+        let limits = |max_path_segments| Limits {
+            max_path_segments,
+            ..Limits::default()
+        };
+        assert_eq!(exceeded(RECTS, limits(1000)), None, "few segments");
+        assert_eq!(
+            exceeded(RECTS, limits(8)),
+            Some(Limit::PathSegments),
+            "the stroke is counted separately"
+        );
+    }
+
+    #[test]
+    fn nested_svg_depth() {
+        // A 10x10 black square, as a base64-encoded SVG.
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <image width="10" height="10" href="data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHdpZHRoPSIxMCIgaGVpZ2h0PSIxMCI+PHJlY3Qgd2lkdGg9IjEwIiBoZWlnaHQ9IjEwIi8+PC9zdmc+"/>
+        </svg>"#;
+        let limits = |max_nested_svg_depth| Limits {
+            max_nested_svg_depth,
+            ..Limits::default()
+        };
+        assert_eq!(exceeded(svg, limits(1)), None, "one nested document");
+        assert_eq!(
+            exceeded(svg, limits(0)),
+            Some(Limit::NestedSvgDepth),
+            "no nested documents are allowed"
+        );
+    }
+
+    #[cfg(feature = "image_format_png")]
+    #[test]
+    fn images() {
+        // A 2x2 red PNG.
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <image width="10" height="10" href="data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAYAAABytg0kAAAAEUlEQVR4nGP4z8DwH4QZYAwAR8oH+WdZbrcAAAAASUVORK5CYII="/>
+        </svg>"#;
+        assert_eq!(exceeded(svg, Limits::default()), None, "a small image");
+        assert_eq!(
+            exceeded(
+                svg,
+                Limits {
+                    max_image_pixels: 3,
+                    ..Limits::default()
+                }
+            ),
+            Some(Limit::ImagePixels),
+            "the image has four pixels"
+        );
+        assert_eq!(
+            exceeded(
+                svg,
+                Limits {
+                    max_decoded_bytes: 15,
+                    ..Limits::default()
+                }
+            ),
+            Some(Limit::DecodedBytes),
+            "the image decodes to sixteen bytes"
+        );
+    }
+
+    #[test]
+    fn untrusted() {
+        assert_eq!(
+            exceeded(RECTS, Limits::UNTRUSTED),
+            None,
+            "typical documents are within the limits"
+        );
+    }
+
+    #[test]
+    fn unlimited() {
+        assert_eq!(
+            Limits::default(),
+            Limits::UNLIMITED,
+            "nothing is limited by default"
+        );
+        assert_eq!(
+            exceeded(RECTS, Limits::UNLIMITED),
+            None,
+            "nothing is limited"
+        );
+    }
+}
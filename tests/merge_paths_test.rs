@@ -0,0 +1,168 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(
+    missing_docs,
+    reason = "tests for vello_svg::RenderOptions::merge_paths"
+)]
+#[cfg(test)]
+pub mod merge_paths_test {
+    use vello_svg::{DefaultErrorHandler, RenderOptions, RenderedSvg, usvg};
+
+    fn render(body: &str, merge_paths: bool) -> RenderedSvg {
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{body}</svg>"#
+        );
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            merge_paths,
+            ..Default::default()
+        };
+        vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap()
+    }
+
+    #[test]
+    fn merges_runs() {
+        let body = r#"
+            <rect x="0" width="10" height="10" fill="red"/>
+            <rect x="11" width="10" height="10" fill="red"/>
+            <rect x="22" width="10" height="10" fill="red"/>
+            <rect x="33" width="10" height="10" fill="blue"/>
+            <rect x="44" width="10" height="10" fill="blue"/>"#;
+        let expected = render(body, false);
+        let merged = render(body, true);
+        assert_eq!(merged.stats.paths, 2, "one fill for each color");
+        assert_eq!(merged.stats.merged_paths, 3, "the paths after the first");
+        assert_eq!(
+            merged.scene.encoding().path_data,
+            expected.scene.encoding().path_data,
+            "the same segments are encoded"
+        );
+        assert_eq!(
+            merged.stats.path_segments, expected.stats.path_segments,
+            "all path segments are counted"
+        );
+        assert_eq!(expected.stats.merged_paths, 0, "merging is off by default");
+    }
+
+    #[test]
+    fn keeps_overlap_sensitive_paths() {
+        for body in [
+            // Overlapping.
+            r#"<rect width="10" height="10" fill="red"/>
+               <rect x="5" width="10" height="10" fill="red"/>"#,
+            // Touching.
+            r#"<rect width="10" height="10" fill="red"/>
+               <rect x="10" width="10" height="10" fill="red"/>"#,
+            // Less than a pixel apart.
+            r#"<rect width="10" height="10" fill="red"/>
+               <rect x="10.5" width="10" height="10" fill="red"/>"#,
+            // More than a unit, but less than a pixel apart after the transform.
+            r#"<g transform="scale(0.5)">
+                 <rect width="10" height="10" fill="red"/>
+                 <rect x="11.5" width="10" height="10" fill="red"/>
+               </g>"#,
+            // Stroked.
+            r#"<rect width="10" height="10" fill="red" stroke="black"/>
+               <rect x="20" width="10" height="10" fill="red" stroke="black"/>"#,
+            // Translucent.
+            r#"<rect width="10" height="10" fill="red" fill-opacity="0.5"/>
+               <rect x="20" width="10" height="10" fill="red" fill-opacity="0.5"/>"#,
+            // Different fill rules.
+            r#"<rect width="10" height="10" fill="red"/>
+               <rect x="20" width="10" height="10" fill="red" fill-rule="evenodd"/>"#,
+        ] {
+            let merged = render(body, true);
+            assert_eq!(merged.stats.merged_paths, 0, "not merged: {body}");
+            assert_eq!(
+                merged.stats.paths,
+                render(body, false).stats.paths,
+                "drawn separately: {body}"
+            );
+        }
+    }
+
+    /// Merged paths draw exactly the same pixels as the separate paths, which is checked by
+    /// rasterizing both with resvg, as vello can't be run without a GPU here.
+    #[cfg(feature = "resvg")]
+    #[test]
+    fn same_pixels() {
+        use resvg::tiny_skia::{Pixmap, Transform};
+
+        fn rasterize(body: &str) -> Pixmap {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{body}</svg>"#
+            );
+            let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+            let mut pixmap = Pixmap::new(100, 100).unwrap();
+            resvg::render(&tree, Transform::identity(), &mut pixmap.as_mut());
+            pixmap
+        }
+
+        // Pairs of paths, in a group with a transform, and whether they can be merged.
+        for (transform, paths, merged) in [
+            ("", ["M0.3 0.3h10v10h-10z", "M11.3 0.3h10v10h-10z"], true),
+            ("", ["M0.5 0.5h10v10h-10z", "M10.5 0.5h10v10h-10z"], false),
+            ("", ["M0 0l10 10h-10z", "M11 0l10 10h-10z"], true),
+            (
+                "scale(2.5)",
+                ["M0 0h10v10h-10z", "M10.5 0h10v10h-10z"],
+                true,
+            ),
+            (
+                "scale(2.5)",
+                ["M0 0h10v10h-10z", "M10.2 0h10v10h-10z"],
+                false,
+            ),
+        ] {
+            let separate = format!(
+                r#"<g transform="{transform}"><path d="{}" fill="red"/><path d="{}" fill="red"/></g>"#,
+                paths[0], paths[1]
+            );
+            let combined = format!(
+                r#"<g transform="{transform}"><path d="{} {}" fill="red"/></g>"#,
+                paths[0], paths[1]
+            );
+            let same = rasterize(&separate).data() == rasterize(&combined).data();
+            assert_eq!(
+                render(&separate, true).stats.merged_paths,
+                usize::from(merged),
+                "merged: {separate}"
+            );
+            if merged {
+                assert!(same, "merged paths draw different pixels: {separate}");
+            }
+        }
+        // Paths which share pixels are antialiased differently when drawn as one fill.
+        let touching = ["M0.5 0.5h10v10h-10z", "M10.5 0.5h10v10h-10z"];
+        assert_ne!(
+            rasterize(&format!(
+                r#"<path d="{}" fill="red"/><path d="{}" fill="red"/>"#,
+                touching[0], touching[1]
+            ))
+            .data(),
+            rasterize(&format!(
+                r#"<path d="{} {}" fill="red"/>"#,
+                touching[0], touching[1]
+            ))
+            .data(),
+            "touching paths draw the same pixels"
+        );
+    }
+
+    #[test]
+    fn unchanged_without_option() {
+        let body = r#"
+            <rect width="10" height="10" fill="red"/>
+            <rect x="20" width="10" height="10" fill="red"/>"#;
+        let rendered = render(body, false);
+        let expected = vello_svg::render(&format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{body}</svg>"#
+        ))
+        .unwrap();
+        let (a, b) = (rendered.scene.encoding(), expected.scene.encoding());
+        assert!(a.draw_tags == b.draw_tags, "draw tags differ");
+        assert_eq!(a.draw_data, b.draw_data, "draw data differs");
+        assert_eq!(a.n_paths, b.n_paths, "path counts differ");
+    }
+}
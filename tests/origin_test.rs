@@ -0,0 +1,185 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RenderOptions::origin")]
+#[cfg(test)]
+pub mod origin_test {
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::{Affine, Point, Rect, Size};
+    use vello_svg::{
+        DefaultErrorHandler, Error, ErrorHandler, RenderOptions, UnsupportedNode, usvg,
+    };
+
+    /// A map tile with coordinates around a million units from the origin.
+    const TILE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <clipPath id="clip"><rect x="1000000" y="2000000" width="40" height="40"/></clipPath>
+        <g clip-path="url(#clip)">
+            <path d="M1000000.25 2000000.25 h10.5 v10.5 z" fill="green"/>
+        </g>
+        <g opacity="0.5">
+            <rect x="1000020" y="2000020" width="10" height="10" stroke="black"/>
+        </g>
+    </svg>"#;
+
+    fn render(svg: &str, origin: Option<Point>) -> Scene {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            origin,
+            ..Default::default()
+        };
+        vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+            .unwrap()
+            .scene
+    }
+
+    /// The path data of a scene, as the 32-bit floats Vello encodes it as.
+    fn coordinates(scene: &Scene) -> Vec<f32> {
+        scene
+            .encoding()
+            .path_data
+            .iter()
+            .map(|&bits| f32::from_bits(bits))
+            .collect()
+    }
+
+    #[test]
+    fn small_coordinates() {
+        let scene = render(TILE, Some(Point::new(1_000_000.0, 2_000_000.0)));
+        let coordinates = coordinates(&scene);
+        assert!(!coordinates.is_empty(), "paths are encoded");
+        assert!(
+            coordinates.iter().all(|c| c.abs() < 100.0),
+            "paths are encoded relative to their centers: {coordinates:?}"
+        );
+        for transform in &scene.encoding().transforms {
+            let [.., x, y] = transform.matrix;
+            assert!(
+                x.abs() < 100.0 && y.abs() < 100.0,
+                "the transforms are relative to the origin: {transform:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_fractions() {
+        let scene = render(TILE, Some(Point::new(1_000_000.0, 2_000_000.0)));
+        let coordinates = coordinates(&scene);
+        assert!(
+            coordinates.contains(&-5.25) && coordinates.contains(&5.25),
+            "the quarter units of the triangle are kept: {coordinates:?}"
+        );
+    }
+
+    #[test]
+    fn absolute_without_origin() {
+        let coordinates = coordinates(&render(TILE, None));
+        assert!(
+            coordinates.iter().any(|c| c.abs() > 1e6),
+            "the coordinates are encoded as they are"
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let tree = usvg::Tree::from_str(TILE, &usvg::Options::default()).unwrap();
+        let origin = Point::new(1_000_000.0, 2_000_000.0);
+        let options = RenderOptions {
+            origin: Some(origin),
+            ..Default::default()
+        };
+        let absolute = vello_svg::render_tree_with_options(
+            &tree,
+            &RenderOptions::default(),
+            &mut DefaultErrorHandler,
+        )
+        .unwrap()
+        .bounds;
+        let rendered =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+        assert_eq!(
+            rendered.bounds,
+            absolute - origin.to_vec2(),
+            "the bounds are moved with the scene"
+        );
+        assert!(
+            rendered.bounds.x0.abs() < 100.0 && rendered.bounds.y0.abs() < 100.0,
+            "the bounds are near the origin: {:?}",
+            rendered.bounds
+        );
+        #[cfg(feature = "rayon")]
+        assert_eq!(
+            vello_svg::render_tree_parallel(&tree, &options)
+                .unwrap()
+                .bounds,
+            rendered.bounds,
+            "the bounds are moved when rendering in parallel"
+        );
+    }
+
+    #[test]
+    fn node_bounds() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect id="rect" x="1000020" y="2000020" width="10" height="10"/>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            origin: Some(Point::new(1_000_000.0, 2_000_000.0)),
+            ..Default::default()
+        };
+        let in_place =
+            vello_svg::render_node_by_id(&tree, "rect", false, &options, &mut DefaultErrorHandler)
+                .unwrap();
+        assert_eq!(
+            in_place.bounds,
+            Rect::new(20.0, 20.0, 30.0, 30.0),
+            "the node is moved by the origin"
+        );
+        let moved =
+            vello_svg::render_node_by_id(&tree, "rect", true, &options, &mut DefaultErrorHandler)
+                .unwrap();
+        assert_eq!(
+            moved.bounds,
+            Rect::new(-1_000_000.0, -2_000_000.0, -999_990.0, -1_999_990.0),
+            "the node is moved to its own origin, and then by the origin"
+        );
+        assert_eq!(moved.size, Size::new(10.0, 10.0), "the size of the node");
+    }
+
+    #[test]
+    fn unsupported_transform() {
+        struct Transforms(Vec<Affine>);
+
+        impl ErrorHandler for Transforms {
+            fn on_unsupported(
+                &mut self,
+                _scene: &mut Scene,
+                unsupported: &UnsupportedNode<'_>,
+            ) -> Result<(), Error> {
+                self.0.push(unsupported.transform);
+                Ok(())
+            }
+        }
+
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <pattern id="pattern" width="2" height="2" patternUnits="userSpaceOnUse">
+                <rect width="1" height="1"/>
+            </pattern>
+            <rect x="40" y="40" width="10" height="10" fill="url(#pattern)"/>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        for origin in [Point::ZERO, Point::new(40.0, 40.0)] {
+            let options = RenderOptions {
+                origin: Some(origin),
+                ..Default::default()
+            };
+            let mut transforms = Transforms(Vec::new());
+            vello_svg::render_tree_with_options(&tree, &options, &mut transforms).unwrap();
+            assert_eq!(
+                transforms.0,
+                [Affine::translate(-origin.to_vec2())],
+                "the transform maps the node to the scene, without the offset the path is encoded \
+                 relative to"
+            );
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::PaintOverride")]
+#[cfg(test)]
+pub mod paint_override_test {
+    use std::sync::Arc;
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::vello::peniko::{Brush, Color};
+    use vello_svg::{
+        DefaultErrorHandler, InvertLightness, MapPalette, PaintOverride, RenderOptions,
+        ReplaceColors, usvg,
+    };
+
+    fn document(fill: &str, stroke: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                <linearGradient id="gradient">
+                    <stop offset="0" stop-color="{fill}"/>
+                    <stop offset="1" stop-color="{stroke}"/>
+                </linearGradient>
+                <rect width="5" height="5" fill="{fill}" fill-opacity="0.5" stroke="{stroke}"/>
+                <rect x="10" width="5" height="5" fill="url(#gradient)"/>
+            </svg>"#
+        )
+    }
+
+    fn render(svg: &str, paint_override: Option<Arc<dyn PaintOverride>>) -> Scene {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            paint_override,
+            ..Default::default()
+        };
+        vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+            .unwrap()
+            .scene
+    }
+
+    fn assert_same(a: &Scene, b: &Scene, message: &str) {
+        let (a, b) = (a.encoding(), b.encoding());
+        assert!(a.draw_tags == b.draw_tags, "{message}: draws differ");
+        assert_eq!(a.draw_data, b.draw_data, "{message}: draw data differs");
+    }
+
+    #[test]
+    fn replace_colors() {
+        let replaced = render(
+            &document("red", "green"),
+            Some(Arc::new(ReplaceColors(palette::css::BLUE))),
+        );
+        assert_same(
+            &replaced,
+            &render(&document("blue", "blue"), None),
+            "every color is blue, keeping its opacity",
+        );
+    }
+
+    #[test]
+    fn map_palette() {
+        let palette = MapPalette::new(
+            [palette::css::RED, palette::css::GREEN],
+            [palette::css::GREEN, palette::css::YELLOW],
+        );
+        let mapped = render(&document("red", "green"), Some(Arc::new(palette)));
+        assert_same(
+            &mapped,
+            &render(&document("green", "yellow"), None),
+            "colors and gradient stops are mapped once",
+        );
+        let unmapped = render(
+            &document("blue", "white"),
+            Some(Arc::new(MapPalette::default())),
+        );
+        assert_same(
+            &unmapped,
+            &render(&document("blue", "white"), None),
+            "other colors are unchanged",
+        );
+    }
+
+    #[test]
+    fn invert_lightness() {
+        let (brush, _) = vello_svg::util::to_brush_with(
+            &usvg::Paint::Color(usvg::Color::black()),
+            usvg::Opacity::ONE,
+            |color| InvertLightness.map_color(color),
+        )
+        .unwrap();
+        let Brush::Solid(color) = brush else {
+            panic!("expected a solid color");
+        };
+        let [r, g, b, a] = color.to_rgba8().to_u8_array();
+        assert!(
+            r > 250 && g > 250 && b > 250 && a == 255,
+            "black becomes white, got {color:?}"
+        );
+        assert_eq!(
+            InvertLightness
+                .map_color(Color::WHITE)
+                .to_rgba8()
+                .to_u8_array()[..3],
+            [0, 0, 0],
+            "white becomes black"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "image_format_png")]
+    fn image_tint() {
+        // A 2x2 red PNG.
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <image width="10" height="10" href="data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAYAAABytg0kAAAAEUlEQVR4nGP4z8DwH4QZYAwAR8oH+WdZbrcAAAAASUVORK5CYII="/>
+        </svg>"#;
+        let plain = render(svg, Some(Arc::new(InvertLightness)));
+        let tinted = render(svg, Some(Arc::new(ReplaceColors(palette::css::BLUE))));
+        let encoding = tinted.encoding();
+        assert_eq!(
+            encoding.n_paths,
+            plain.encoding().n_paths + 5,
+            "the tint, and two layers which are counted at both ends"
+        );
+        assert_eq!(encoding.n_open_clips, 0, "layers are balanced");
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::render_tree_parallel")]
+#[cfg(all(test, feature = "rayon"))]
+pub mod parallel_test {
+    use std::fmt::Write as _;
+    use vello_svg::vello::Scene;
+    use vello_svg::{DefaultErrorHandler, Limits, RenderOptions, usvg};
+
+    /// A document with many top level elements, some of which share transforms and styles.
+    fn document() -> usvg::Tree {
+        let mut svg = String::from(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200" viewBox="0 0 100 100">
+                <linearGradient id="gradient">
+                    <stop offset="0" stop-color="red"/>
+                    <stop offset="1" stop-color="blue"/>
+                </linearGradient>
+                <pattern id="pattern" width="2" height="2"><rect width="1" height="1"/></pattern>"#,
+        );
+        for i in 0..200 {
+            let (x, y) = (i % 20 * 5, i / 20 * 10);
+            match i % 5 {
+                0 => write!(svg, r#"<rect x="{x}" y="{y}" width="4" height="4" fill="green"/>"#),
+                1 => write!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="4" height="4" stroke="black" fill="url(#gradient)"/>"#
+                ),
+                2 => write!(
+                    svg,
+                    r#"<g opacity="0.5" transform="rotate(10)"><circle cx="{x}" cy="{y}" r="2"/></g>"#
+                ),
+                3 => write!(
+                    svg,
+                    r#"<rect id="patterned-{i}" x="{x}" y="{y}" width="4" height="4" fill="url(#pattern)"/>"#
+                ),
+                _ => write!(svg, r#"<path d="M{x} {y} h4 v4 z" fill-rule="evenodd"/>"#),
+            }
+            .unwrap();
+        }
+        svg.push_str("</svg>");
+        usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap()
+    }
+
+    /// The transform and style each path is drawn with.
+    ///
+    /// Transforms and styles are only encoded when they change, so where they are encoded can
+    /// differ between scenes which draw the same.
+    fn path_states(scene: &Scene) -> impl PartialEq + std::fmt::Debug + '_ {
+        let encoding = scene.encoding();
+        let (mut transform, mut style) = (None, None);
+        let (mut transforms, mut styles) = (encoding.transforms.iter(), encoding.styles.iter());
+        let mut states = Vec::new();
+        for tag in &encoding.path_tags {
+            // The values of `PathTag::TRANSFORM`, `PathTag::STYLE` and `PathTag::PATH`.
+            match tag.0 {
+                0x20 => transform = transforms.next(),
+                0x40 => style = styles.next(),
+                0x10 => states.push((transform, style)),
+                _ => {}
+            }
+        }
+        states
+    }
+
+    /// Assert that two scenes draw the same paths with the same transforms, styles and paints.
+    fn assert_same(a: &Scene, b: &Scene) {
+        assert_eq!(
+            path_states(a),
+            path_states(b),
+            "paths are drawn differently"
+        );
+        let (a, b) = (a.encoding(), b.encoding());
+        assert_eq!(a.path_data, b.path_data, "path data differs");
+        assert!(a.draw_tags == b.draw_tags, "draw tags differ");
+        assert_eq!(a.draw_data, b.draw_data, "draw data differs");
+        assert_eq!(
+            a.resources.color_stops, b.resources.color_stops,
+            "gradient stops differ"
+        );
+        assert_eq!(a.n_paths, b.n_paths, "path counts differ");
+        assert_eq!(
+            a.n_path_segments, b.n_path_segments,
+            "segment counts differ"
+        );
+        assert_eq!(a.n_clips, b.n_clips, "clip counts differ");
+    }
+
+    #[test]
+    fn same_as_serial() {
+        let tree = document();
+        let options = RenderOptions::default();
+        let serial =
+            vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler).unwrap();
+        let parallel = vello_svg::render_tree_parallel(&tree, &options).unwrap();
+        assert_same(&parallel.scene, &serial.scene);
+        assert_eq!(parallel.report, serial.report, "the reports differ");
+        let (mut parallel_stats, mut serial_stats) = (parallel.stats, serial.stats);
+        parallel_stats.encode_time = None;
+        serial_stats.encode_time = None;
+        assert_eq!(parallel_stats, serial_stats, "the statistics differ");
+    }
+
+    #[test]
+    fn exceeded_limit() {
+        let tree = document();
+        let all_limits = [
+            Limits {
+                max_nodes: 150,
+                ..Limits::default()
+            },
+            Limits {
+                max_path_segments: 300,
+                ..Limits::default()
+            },
+        ];
+        for limits in all_limits {
+            let options = RenderOptions {
+                limits,
+                ..Default::default()
+            };
+            let serial =
+                vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+                    .unwrap_err();
+            let parallel = vello_svg::render_tree_parallel(&tree, &options).unwrap_err();
+            assert_eq!(
+                parallel.to_string(),
+                serial.to_string(),
+                "the same node exceeds the limit"
+            );
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RasterCache")]
+#[cfg(all(test, feature = "resvg"))]
+pub mod raster_cache_test {
+    use std::sync::Arc;
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::Rect;
+    use vello_svg::vello::peniko::ImageBrush;
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::{RasterCache, usvg};
+
+    /// A 10 by 10 icon, half of which is covered by a red square.
+    fn icon() -> Arc<usvg::Tree> {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <rect width="10" height="5" fill="red"/>
+        </svg>"#;
+        Arc::new(usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap())
+    }
+
+    #[test]
+    fn rasterizes_once() {
+        let svg = icon();
+        let mut cache = RasterCache::new(usize::MAX);
+        let first = cache.get(&svg, 20, 20, None).unwrap().image.data.id();
+        let second = cache.get(&svg, 20, 20, None).unwrap().image.data.id();
+        assert_eq!(first, second, "the image is reused");
+        assert_eq!(cache.len(), 1, "one image is cached");
+        assert_eq!(cache.bytes(), 20 * 20 * 4, "the size of the image");
+
+        let image = &cache.get(&svg, 40, 20, None).unwrap().image;
+        assert_eq!(
+            (image.width, image.height),
+            (40, 20),
+            "another size is rasterized"
+        );
+        assert_eq!(cache.len(), 2, "both sizes are cached");
+        assert!(cache.get(&svg, 0, 20, None).is_none(), "nothing to draw");
+    }
+
+    #[test]
+    fn pixels() {
+        let svg = icon();
+        let mut cache = RasterCache::new(usize::MAX);
+        let data = cache.get(&svg, 10, 10, None).unwrap().image.data.clone();
+        assert_eq!(&data.data()[..4], &[255, 0, 0, 255], "the top is red");
+        assert_eq!(
+            &data.data()[data.len() - 4..],
+            &[0, 0, 0, 0],
+            "the bottom is transparent"
+        );
+
+        let tinted = cache
+            .get(&svg, 10, 10, Some(palette::css::BLUE.with_alpha(0.5)))
+            .unwrap()
+            .image
+            .data
+            .clone();
+        assert_eq!(
+            &tinted.data()[..4],
+            &[0, 0, 128, 128],
+            "the top is half transparent blue, premultiplied"
+        );
+        assert_eq!(cache.len(), 2, "each color is cached separately");
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let (a, b) = (icon(), icon());
+        let image_bytes = 10 * 10 * 4;
+        let mut cache = RasterCache::new(2 * image_bytes);
+        let id = |image: Option<&ImageBrush>| image.unwrap().image.data.id();
+        let first_a = id(cache.get(&a, 10, 10, None));
+        let first_b = id(cache.get(&b, 10, 10, None));
+        cache.get(&a, 10, 10, None);
+        cache.get(&a, 10, 10, Some(palette::css::BLUE));
+        assert_eq!(cache.len(), 2, "only two images fit");
+        assert_eq!(cache.bytes(), 2 * image_bytes, "the size of two images");
+        assert_eq!(
+            id(cache.get(&a, 10, 10, None)),
+            first_a,
+            "the recently used image is kept"
+        );
+        assert_ne!(
+            id(cache.get(&b, 10, 10, None)),
+            first_b,
+            "the least recently used image was evicted"
+        );
+
+        cache.clear();
+        assert!(cache.is_empty(), "all images were evicted");
+        assert_eq!(cache.bytes(), 0, "no memory is used");
+    }
+
+    #[test]
+    fn append_to() {
+        let svg = icon();
+        let mut cache = RasterCache::new(usize::MAX);
+        let mut scene = Scene::new();
+        cache.append_to(&mut scene, &svg, Rect::new(0.0, 0.0, 40.0, 20.0), 2.0, None);
+        assert_eq!(scene.encoding().n_paths, 1, "one image is drawn");
+        assert!(
+            cache.get(&svg, 40, 40, None).is_some() && cache.len() == 1,
+            "the icon was rasterized at its size in device pixels"
+        );
+    }
+}
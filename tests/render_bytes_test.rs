@@ -0,0 +1,111 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for the byte and file entry points")]
+#[cfg(test)]
+pub mod render_bytes_test {
+    use vello_svg::{Error, usvg};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 40 20">
+        <rect width="40" height="20" fill="blue"/>
+    </svg>"#;
+
+    /// [`SVG`] compressed with gzip.
+    const SVGZ: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x4d, 0x8d, 0xc1, 0x0e, 0x83,
+        0x30, 0x0c, 0x43, 0xef, 0xfb, 0x8a, 0x28, 0x1f, 0x40, 0x32, 0xc6, 0x09, 0x35, 0x1c, 0xf8,
+        0x94, 0x6d, 0xa5, 0xad, 0x54, 0x06, 0x82, 0x8c, 0xf0, 0xf9, 0x2b, 0xda, 0x26, 0xe1, 0x93,
+        0x25, 0xfb, 0xd9, 0x6e, 0xdd, 0x02, 0xec, 0x63, 0x7e, 0xad, 0x82, 0x51, 0x75, 0x6e, 0x89,
+        0xcc, 0xac, 0xb2, 0x5b, 0x35, 0x2d, 0x81, 0x6a, 0x66, 0xa6, 0xd2, 0x40, 0xb0, 0xf4, 0xd4,
+        0x28, 0x58, 0x33, 0x42, 0xf4, 0x29, 0x44, 0x15, 0xbc, 0x16, 0xbf, 0x25, 0x6f, 0xfd, 0xb4,
+        0x0b, 0x32, 0x30, 0x34, 0x0c, 0x25, 0xef, 0x2e, 0xf0, 0x93, 0x5b, 0xfc, 0x43, 0xff, 0x64,
+        0x73, 0x22, 0x8f, 0x95, 0x21, 0xe5, 0x2c, 0x78, 0xcf, 0x6f, 0x8f, 0xf4, 0x45, 0xdc, 0xf1,
+        0xd4, 0x7d, 0x00, 0x60, 0x34, 0xda, 0x01, 0x91, 0x00, 0x00, 0x00,
+    ];
+
+    /// A 2x2 red PNG.
+    #[cfg(feature = "image_format_png")]
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x72,
+        0xb6, 0x0d, 0x24, 0x00, 0x00, 0x00, 0x11, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8,
+        0xcf, 0xc0, 0xf0, 0x1f, 0x84, 0x19, 0x60, 0x0c, 0x00, 0x47, 0xca, 0x07, 0xf9, 0x67, 0x59,
+        0x6e, 0xb7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn bytes_match_str() {
+        let from_str = vello_svg::render(SVG).unwrap();
+        let from_bytes = vello_svg::render_bytes(SVG.as_bytes()).unwrap();
+        assert_eq!(from_str.size, from_bytes.size, "sizes differ");
+        assert_eq!(from_str.view_box, from_bytes.view_box, "view boxes differ");
+        assert_eq!(
+            from_str.scene.encoding().draw_data,
+            from_bytes.scene.encoding().draw_data,
+            "encodings differ"
+        );
+    }
+
+    #[test]
+    fn svgz_matches_str() {
+        let from_str = vello_svg::render(SVG).unwrap();
+        let from_svgz = vello_svg::render_bytes(SVGZ).unwrap();
+        assert_eq!(from_str.size, from_svgz.size, "sizes differ");
+        assert_eq!(from_str.view_box, from_svgz.view_box, "view boxes differ");
+        let (a, b) = (from_str.scene.encoding(), from_svgz.scene.encoding());
+        assert_eq!(a.path_data, b.path_data, "paths differ");
+        assert_eq!(a.draw_data, b.draw_data, "draws differ");
+    }
+
+    #[test]
+    fn malformed_gzip() {
+        let result = vello_svg::render_bytes(&[0x1f, 0x8b, 0x00, 0x00]);
+        assert!(
+            matches!(result, Err(Error::Decompression)),
+            "expected a decompression error, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn not_utf8() {
+        let result = vello_svg::render_bytes(&[0xff, 0xfe, 0x3c, 0x00]);
+        assert!(
+            matches!(result, Err(Error::Svg(usvg::Error::NotAnUtf8Str))),
+            "expected a UTF-8 error, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        let result = vello_svg::render_file("this/file/does/not/exist.svg");
+        assert!(
+            matches!(result, Err(Error::Io(_))),
+            "expected an I/O error, got {result:?}"
+        );
+    }
+
+    #[cfg(feature = "image_format_png")]
+    #[test]
+    fn relative_image() {
+        let dir =
+            std::env::temp_dir().join(format!("vello_svg-render_file-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("images")).unwrap();
+        std::fs::write(dir.join("images/red.png"), PNG).unwrap();
+        std::fs::write(
+            dir.join("image.svg"),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <image width="10" height="10" href="images/red.png"/>
+            </svg>"#,
+        )
+        .unwrap();
+        let result = vello_svg::render_file(dir.join("image.svg"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let rendered = result.unwrap();
+        assert_eq!(rendered.stats.images, 1, "the image is drawn");
+        assert_eq!(
+            rendered.stats.decoded_image_bytes, 16,
+            "the image has four pixels"
+        );
+        assert!(rendered.report.is_empty(), "nothing is unsupported");
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::render_node_by_id")]
+#[cfg(test)]
+pub mod render_node_test {
+    use vello_svg::vello::kurbo::{Rect, Size};
+    use vello_svg::{DefaultErrorHandler, Error, RenderOptions, usvg};
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+        <g id="first">
+            <rect width="100" height="100" fill="red"/>
+            <rect width="50" height="50" fill="green"/>
+        </g>
+        <g id="second" transform="translate(100 0)" opacity="0.5">
+            <rect id="icon" x="5" width="10" height="10" fill="blue"/>
+        </g>
+    </svg>"#;
+
+    fn render(id: &str, re_origin: bool) -> Result<vello_svg::RenderedSvg, Error> {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        vello_svg::render_node_by_id(
+            &tree,
+            id,
+            re_origin,
+            &RenderOptions::default(),
+            &mut DefaultErrorHandler,
+        )
+    }
+
+    #[test]
+    fn in_place() {
+        let rendered = render("icon", false).unwrap();
+        assert_eq!(rendered.size, Size::new(200.0, 100.0), "the document size");
+        assert_eq!(
+            rendered.bounds,
+            Rect::new(105.0, 0.0, 115.0, 10.0),
+            "the ancestor's transform is applied"
+        );
+        let encoding = rendered.scene.encoding();
+        assert_eq!(
+            encoding.n_paths, 3,
+            "the icon, and the ancestor's layer which is counted at both ends"
+        );
+        assert_eq!(encoding.n_open_clips, 0, "layers are balanced");
+    }
+
+    #[test]
+    fn re_origin() {
+        let rendered = render("icon", true).unwrap();
+        assert_eq!(rendered.size, Size::new(10.0, 10.0), "the size of the icon");
+        assert_eq!(
+            rendered.bounds,
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            "the icon is moved to the origin"
+        );
+    }
+
+    #[test]
+    fn group() {
+        let rendered = render("first", true).unwrap();
+        assert_eq!(
+            rendered.size,
+            Size::new(100.0, 100.0),
+            "the size of the group"
+        );
+        assert_eq!(
+            rendered.scene.encoding().n_paths,
+            4,
+            "the two children, and the group's layer which is counted at both ends"
+        );
+    }
+
+    #[test]
+    fn not_found() {
+        let result = render("missing", false);
+        assert!(
+            matches!(&result, Err(Error::NodeNotFound { id }) if id == "missing"),
+            "expected a not found error, got {result:?}"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RenderedSvg")]
+#[cfg(test)]
+pub mod rendered_test {
+    use vello::kurbo::{Rect, Size};
+    use vello_svg::usvg;
+
+    /// A document whose view box is scaled by a half and doesn't start at the origin.
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50" viewBox="10 20 200 100">
+        <rect x="30" y="40" width="20" height="10"/>
+    </svg>"#;
+
+    #[test]
+    fn render() {
+        let rendered = vello_svg::render(SVG).unwrap();
+        assert_eq!(
+            rendered.size,
+            Size::new(100.0, 50.0),
+            "the width and height"
+        );
+        assert_eq!(
+            rendered.view_box,
+            Some(Rect::new(10.0, 20.0, 210.0, 120.0)),
+            "the view box of the root element"
+        );
+        assert_eq!(
+            rendered.bounds,
+            Rect::new(10.0, 10.0, 20.0, 15.0),
+            "the rectangle, in the coordinate space of the size"
+        );
+    }
+
+    #[test]
+    fn render_tree() {
+        let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+        let rendered = vello_svg::render_tree(&tree);
+        assert_eq!(
+            rendered.size,
+            Size::new(100.0, 50.0),
+            "the width and height"
+        );
+        assert_eq!(
+            rendered.view_box, None,
+            "the tree has already applied the view box"
+        );
+        assert_eq!(
+            rendered.bounds,
+            Rect::new(10.0, 10.0, 20.0, 15.0),
+            "the rectangle, in the coordinate space of the size"
+        );
+    }
+
+    #[test]
+    fn without_view_box() {
+        let rendered = vello_svg::render(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="20">
+                <rect x="1" y="2" width="3" height="4" stroke="black" stroke-width="2"/>
+            </svg>"#,
+        )
+        .unwrap();
+        assert_eq!(rendered.size, Size::new(10.0, 20.0), "the width and height");
+        assert_eq!(rendered.view_box, None, "the root has no view box");
+        assert_eq!(
+            rendered.bounds,
+            Rect::new(0.0, 1.0, 5.0, 7.0),
+            "the bounds include the stroke"
+        );
+    }
+}
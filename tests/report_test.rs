@@ -0,0 +1,86 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::analyze")]
+#[cfg(test)]
+pub mod report_test {
+    use vello_svg::{Unsupported, usvg};
+
+    #[test]
+    fn counts_and_ids() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <pattern id="p" width="2" height="2" patternUnits="userSpaceOnUse">
+                <rect width="1" height="1"/>
+            </pattern>
+            <mask id="m"><rect width="5" height="5" fill="white"/></mask>
+            <rect id="both" width="10" height="10" fill="url(#p)" stroke="url(#p)"/>
+            <rect width="10" height="10" fill="url(#p)"/>
+            <g id="masked" mask="url(#m)"><rect width="10" height="10"/></g>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let report = vello_svg::analyze(&tree);
+
+        let patterns = report.get(Unsupported::PatternPaint).unwrap();
+        assert_eq!(patterns.count, 3, "fill and stroke are counted separately");
+        assert_eq!(patterns.node_ids, ["both"], "unnamed nodes have no id");
+        assert_eq!(report.count(Unsupported::Mask), 1, "one masked group");
+        assert_eq!(report.count(Unsupported::Filter), 0, "no filters");
+        assert!(report.has_omissions(), "patterns are omitted");
+
+        let rendered = vello_svg::render(svg).unwrap();
+        assert_eq!(
+            rendered.report, report,
+            "rendering reports the same features"
+        );
+    }
+
+    #[test]
+    fn supported_document() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <rect width="10" height="10" fill="blue"/>
+        </svg>"#;
+        let rendered = vello_svg::render(svg).unwrap();
+        assert!(rendered.report.is_empty(), "got {:?}", rendered.report);
+    }
+
+    #[test]
+    fn nested_content() {
+        // A transformed clip path, and a nested SVG image containing a masked group.
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <clipPath id="c" transform="scale(2)"><rect width="2" height="2"/></clipPath>
+            <g id="clipped" clip-path="url(#c)"><rect width="10" height="10"/></g>
+            <image id="nested" width="10" height="10" href="data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHdpZHRoPSIxMCIgaGVpZ2h0PSIxMCI+PG1hc2sgaWQ9Im0iPjxyZWN0IHdpZHRoPSI1IiBoZWlnaHQ9IjUiIGZpbGw9IndoaXRlIi8+PC9tYXNrPjxnIGlkPSJpbm5lciIgbWFzaz0idXJsKCNtKSI+PHJlY3Qgd2lkdGg9IjEwIiBoZWlnaHQ9IjEwIi8+PC9nPjwvc3ZnPg=="/>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let report = vello_svg::analyze(&tree);
+        assert_eq!(
+            report.get(Unsupported::ClipPath).unwrap().node_ids,
+            ["clipped"],
+            "the clip path is transformed"
+        );
+        assert_eq!(
+            report.get(Unsupported::Mask).unwrap().node_ids,
+            ["inner"],
+            "nested documents are analyzed"
+        );
+        assert_eq!(
+            vello_svg::render(svg).unwrap().report,
+            report,
+            "rendering reports the same features"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <mask id="m"><rect width="5" height="5" fill="white"/></mask>
+            <g id="masked" mask="url(#m)"><rect width="10" height="10"/></g>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let report = vello_svg::analyze(&tree);
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: vello_svg::RenderReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report, "the report survives serialization");
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RetainedSvg")]
+#[cfg(test)]
+pub mod retained_test {
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::{Affine, Rect};
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::{Error, LevelOfDetail, Limit, Limits, RenderOptions, RetainedSvg, usvg};
+
+    fn document(transform: &str, opacity: &str, fill: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20" viewBox="0 0 20 10">
+                <g id="group" transform="{transform}" opacity="{opacity}">
+                    <rect id="a" width="5" height="5" fill="{fill}"/>
+                </g>
+                <rect id="b" x="10" width="5" height="5" fill="green"/>
+            </svg>"#
+        )
+    }
+
+    fn retain(svg: &str) -> RetainedSvg {
+        RetainedSvg::new(usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap())
+    }
+
+    fn encode(svg: &mut RetainedSvg) -> Scene {
+        let mut scene = Scene::new();
+        svg.append_to(&mut scene, Affine::IDENTITY).unwrap();
+        scene
+    }
+
+    /// Assert that two scenes have the same content, allowing for rounding in transforms.
+    fn assert_same(a: &Scene, b: &Scene, message: &str) {
+        let (a, b) = (a.encoding(), b.encoding());
+        assert!(a.draw_tags == b.draw_tags, "{message}: draws differ");
+        assert_eq!(a.draw_data, b.draw_data, "{message}: draw data differs");
+        assert_eq!(a.path_data, b.path_data, "{message}: paths differ");
+        assert_eq!(a.transforms.len(), b.transforms.len(), "{message}");
+        for (ta, tb) in a.transforms.iter().zip(&b.transforms) {
+            let ta = ta.matrix.iter().chain(&ta.translation);
+            let tb = tb.matrix.iter().chain(&tb.translation);
+            assert!(
+                ta.zip(tb).all(|(x, y)| (x - y).abs() < 1e-4),
+                "{message}: transforms differ"
+            );
+        }
+    }
+
+    #[test]
+    fn changes_match_the_changed_document() {
+        let mut svg = retain(&document("translate(1 0)", "1", "red"));
+        assert!(svg.is_dirty(), "nothing is encoded yet");
+        encode(&mut svg);
+        assert!(!svg.is_dirty(), "everything is encoded");
+
+        svg.set_fill("a", palette::css::BLUE).unwrap();
+        svg.set_transform("group", Affine::translate((2.0, 1.0)))
+            .unwrap();
+        svg.set_opacity("group", 0.5).unwrap();
+        assert!(svg.is_dirty(), "the first fragment changed");
+        let changed = encode(&mut svg);
+        let expected = encode(&mut retain(&document("translate(2 1)", "0.5", "blue")));
+        assert_same(&changed, &expected, "changed document");
+
+        svg.reset("a").unwrap();
+        svg.reset("group").unwrap();
+        let reset = encode(&mut svg);
+        let original = encode(&mut retain(&document("translate(1 0)", "1", "red")));
+        assert_same(&reset, &original, "reset document");
+    }
+
+    #[test]
+    fn visibility() {
+        let mut svg = retain(&document("", "1", "red"));
+        let before = encode(&mut svg).encoding().n_paths;
+        svg.set_visible("b", false).unwrap();
+        let after = encode(&mut svg).encoding().n_paths;
+        assert_eq!(after, before - 1, "the hidden rect isn't drawn");
+    }
+
+    #[test]
+    fn unknown_id() {
+        let mut svg = retain(&document("", "1", "red"));
+        let result = svg.set_visible("missing", false);
+        assert!(
+            matches!(&result, Err(Error::NodeNotFound { id }) if id == "missing"),
+            "expected a not found error, got {result:?}"
+        );
+        assert!(svg.is_dirty(), "nothing is encoded yet");
+    }
+
+    #[test]
+    fn layer_groups() {
+        let layered = |opacity: &str, fill: &str| {
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                    <g id="layer1" opacity="{opacity}">
+                        <rect id="a" width="5" height="5" fill="{fill}"/>
+                        <rect id="b" x="2" width="5" height="5" fill="green"/>
+                    </g>
+                </svg>"#
+            )
+        };
+        let mut svg = retain(&layered("1", "red"));
+        assert!(
+            format!("{svg:?}").contains("fragments: 2"),
+            "the elements of the layer are fragments: {svg:?}"
+        );
+
+        svg.set_fill("a", palette::css::BLUE).unwrap();
+        let changed = encode(&mut svg);
+        let expected = encode(&mut retain(&layered("1", "blue")));
+        assert_same(&changed, &expected, "changed element of the layer");
+
+        svg.set_opacity("layer1", 0.5).unwrap();
+        assert!(
+            format!("{svg:?}").contains("fragments: 1"),
+            "the changed layer is a fragment: {svg:?}"
+        );
+        let translucent = encode(&mut svg);
+        let expected_translucent = encode(&mut retain(&layered("0.5", "blue")));
+        assert_same(
+            &translucent,
+            &expected_translucent,
+            "the overlapping elements share the layer",
+        );
+
+        svg.reset("layer1").unwrap();
+        svg.reset("a").unwrap();
+        assert!(
+            format!("{svg:?}").contains("fragments: 2"),
+            "the elements of the layer are fragments again: {svg:?}"
+        );
+        let reset = encode(&mut svg);
+        let original = encode(&mut retain(&layered("1", "red")));
+        assert_same(&reset, &original, "reset document");
+    }
+
+    #[test]
+    fn exceeded_limit() {
+        let tree =
+            usvg::Tree::from_str(&document("", "1", "red"), &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            limits: Limits {
+                max_nodes: 1,
+                ..Limits::UNLIMITED
+            },
+            ..Default::default()
+        };
+        let mut svg = RetainedSvg::with_options(tree, options);
+        let mut scene = Scene::new();
+        let result = svg.append_to(&mut scene, Affine::IDENTITY);
+        assert!(
+            matches!(
+                result,
+                Err(Error::LimitExceeded {
+                    limit: Limit::Nodes,
+                    ..
+                })
+            ),
+            "expected the node limit to be exceeded, got {result:?}"
+        );
+        assert_eq!(scene.encoding().n_paths, 0, "nothing is appended");
+        assert!(svg.is_dirty(), "the fragment is encoded again");
+        assert!(svg.update().is_err(), "the error is reported again");
+    }
+
+    #[test]
+    fn culling() {
+        let changed = |options: RenderOptions| {
+            let tree = usvg::Tree::from_str(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+                    <g id="outside"><rect id="moved" x="100" width="5" height="5"/></g>
+                    <g id="small"><rect id="scaled" width="0.01" height="0.01"/></g>
+                </svg>"#,
+                &usvg::Options::default(),
+            )
+            .unwrap();
+            let mut svg = RetainedSvg::with_options(tree, options);
+            svg.set_transform("moved", Affine::translate((-100.0, 0.0)))
+                .unwrap();
+            svg.set_transform("scaled", Affine::scale(500.0)).unwrap();
+            encode(&mut svg)
+        };
+        let expected = changed(RenderOptions::default());
+        for skipping in [
+            RenderOptions {
+                viewport: Some(Rect::new(0.0, 0.0, 20.0, 10.0)),
+                ..Default::default()
+            },
+            RenderOptions {
+                level_of_detail: Some(LevelOfDetail::new(1.0)),
+                ..Default::default()
+            },
+        ] {
+            assert_same(
+                &changed(skipping.clone()),
+                &expected,
+                &format!(
+                    "the changed nodes are drawn, although their groups were skipped before: {skipping:?}"
+                ),
+            );
+        }
+    }
+}
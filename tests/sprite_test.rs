@@ -0,0 +1,133 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::SpriteSheet")]
+#[cfg(test)]
+pub mod sprite_test {
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::{Point, Rect};
+    use vello_svg::vello::peniko::color::palette;
+    use vello_svg::{Error, SpriteSheet};
+
+    // The view box of the document shouldn't affect the symbols.
+    const SHEET: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100" viewBox="0 0 10 10">
+        <defs>
+            <symbol id="square" viewBox="0 0 24 24">
+                <rect x="2" y="2" width="20" height="20"/>
+            </symbol>
+        </defs>
+        <symbol id="dot">
+            <circle cx="50" cy="50" r="10" fill="red"/>
+        </symbol>
+        <symbol><rect width="1" height="1"/></symbol>
+        <rect width="10" height="10" fill="blue"/>
+    </svg>"#;
+
+    #[test]
+    fn symbols() {
+        let sheet = SpriteSheet::new(SHEET).unwrap();
+        let mut ids: Vec<_> = sheet.ids().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["dot", "square"], "symbols without ids are skipped");
+
+        let square = sheet.get("square").unwrap();
+        assert_eq!(
+            square.view_box,
+            Rect::new(0.0, 0.0, 24.0, 24.0),
+            "the symbol's view box"
+        );
+        assert!(
+            square.scene.encoding().n_paths > 0,
+            "the symbol's content is drawn"
+        );
+        assert_eq!(
+            square.scene.encoding().n_open_clips,
+            0,
+            "layers are balanced"
+        );
+
+        let dot = sheet.get("dot").unwrap();
+        assert_eq!(
+            dot.view_box,
+            Rect::new(0.0, 0.0, 20.0, 20.0),
+            "the bounds of the content, moved to the origin"
+        );
+    }
+
+    #[test]
+    fn append() {
+        let sheet = SpriteSheet::new(SHEET).unwrap();
+        let square = sheet.get("square").unwrap();
+        let transform = square.transform(Rect::new(0.0, 0.0, 48.0, 96.0));
+        assert_eq!(
+            transform * Point::new(24.0, 24.0),
+            Point::new(48.0, 72.0),
+            "scaled to fit and centered"
+        );
+
+        let mut scene = Scene::new();
+        sheet
+            .append(
+                &mut scene,
+                "square",
+                Rect::new(0.0, 0.0, 16.0, 16.0),
+                Some(palette::css::ORANGE),
+            )
+            .unwrap();
+        assert_eq!(scene.encoding().n_open_clips, 0, "layers are balanced");
+
+        let result = sheet.append(&mut scene, "missing", Rect::ZERO, None);
+        assert!(
+            matches!(&result, Err(Error::NodeNotFound { id }) if id == "missing"),
+            "expected a not found error, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn self_closing_root() {
+        let svg =
+            r#"<!-- </svg> --><svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#;
+        let sheet = SpriteSheet::new(svg).unwrap();
+        assert!(sheet.is_empty(), "an empty root has no symbols");
+    }
+
+    #[test]
+    fn prefixed_root() {
+        let svg = r#"<?xml version="1.0"?>
+            <!-- A comment with an end tag: </svg:svg> -->
+            <svg:svg xmlns:svg="http://www.w3.org/2000/svg" width="10" height="10">
+                <svg:symbol id="square" viewBox="0 0 24 24">
+                    <svg:rect width="24" height="24"/>
+                </svg:symbol>
+            </svg:svg >"#;
+        let sheet = SpriteSheet::new(svg).unwrap();
+        let square = sheet.get("square").unwrap();
+        assert!(
+            square.scene.encoding().n_paths > 0,
+            "the symbol's content is drawn"
+        );
+    }
+
+    #[test]
+    fn colliding_ids() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <symbol id="square" viewBox="0 0 24 24">
+                <rect width="24" height="24"/>
+            </symbol>
+            <symbol id="vello_svg-sprite0-0"><circle r="5"/></symbol>
+            <rect id="vello_svg-sprite-0" width="10" height="10"/>
+            <rect id="vello_svg-sprite1-1" width="10" height="10"/>
+        </svg>"#;
+        let sheet = SpriteSheet::new(svg).unwrap();
+        assert_eq!(
+            sheet.get("square").unwrap().view_box,
+            Rect::new(0.0, 0.0, 24.0, 24.0),
+            "the right symbol is found"
+        );
+        assert_eq!(
+            sheet.get("vello_svg-sprite0-0").unwrap().view_box,
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            "symbols can have any id"
+        );
+    }
+}
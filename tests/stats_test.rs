@@ -0,0 +1,56 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RenderStats")]
+#[cfg(test)]
+pub mod stats_test {
+    use vello_svg::usvg;
+
+    #[test]
+    fn counts() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+            <linearGradient id="lg">
+                <stop offset="0" stop-color="red"/>
+                <stop offset="0.5" stop-color="green"/>
+                <stop offset="1" stop-color="blue"/>
+            </linearGradient>
+            <g opacity="0.5">
+                <g opacity="0.5">
+                    <rect width="10" height="10" fill="url(#lg)" stroke="black"/>
+                </g>
+            </g>
+            <path d="M0 0 L5 5 L0 5 Z" fill="red"/>
+        </svg>"#;
+        let rendered = vello_svg::render(svg).unwrap();
+        let stats = rendered.stats;
+
+        assert_eq!(stats.groups, 3, "the root and two nested groups");
+        assert_eq!(stats.layers, 2, "one layer per nested group");
+        assert_eq!(stats.max_layer_depth, 2, "the groups are nested");
+        assert_eq!(stats.paths, 3, "a fill and stroke, and another fill");
+        assert_eq!(stats.gradient_stops, 3, "one gradient with three stops");
+        assert_eq!(stats.images, 0, "no images");
+        assert_eq!(
+            stats.path_segments,
+            rendered.scene.encoding().n_path_segments as usize,
+            "all segments in the scene come from the document"
+        );
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            assert!(stats.parse_time.is_some(), "the document was parsed");
+            assert!(stats.encode_time.is_some(), "the document was encoded");
+        }
+    }
+
+    #[test]
+    fn no_parse_time_for_trees() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20"/>"#;
+        let tree = usvg::Tree::from_str(svg, &Default::default()).unwrap();
+        let rendered = vello_svg::render_tree(&tree);
+        assert_eq!(
+            rendered.stats.parse_time, None,
+            "the tree was parsed elsewhere"
+        );
+        assert_eq!(rendered.stats.groups, 1, "only the root group");
+    }
+}
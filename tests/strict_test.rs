@@ -0,0 +1,97 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for strict mode")]
+#[cfg(test)]
+pub mod strict_test {
+    use vello::Scene;
+    use vello_svg::{
+        DefaultErrorHandler, Error, Limit, Limits, RenderOptions, StrictErrorHandler, usvg,
+    };
+
+    #[test]
+    fn pattern_fill() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <pattern id="p" width="2" height="2" patternUnits="userSpaceOnUse">
+                <rect width="1" height="1"/>
+            </pattern>
+            <rect id="patterned" width="10" height="10" fill="url(#p)"/>
+        </svg>"#;
+        assert!(
+            vello_svg::render(svg).is_ok(),
+            "lenient mode should succeed"
+        );
+        let result = vello_svg::render_strict(svg);
+        assert!(
+            matches!(&result, Err(Error::UnsupportedPaint { id }) if id == "patterned"),
+            "expected an unsupported paint error, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn mask() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <mask id="m"><rect width="5" height="5" fill="white"/></mask>
+            <g id="masked" mask="url(#m)"><rect width="10" height="10"/></g>
+        </svg>"#;
+        let result = vello_svg::render_strict(svg);
+        assert!(
+            matches!(&result, Err(Error::UnsupportedMask { id }) if id == "masked"),
+            "expected an unsupported mask error, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn nesting_depth() {
+        // Parsing such a deeply nested document needs more stack than the default in debug builds.
+        std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(nesting_depth_inner)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    fn nesting_depth_inner() {
+        let depth = 300;
+        let mut svg =
+            String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">"#);
+        for i in 0..depth {
+            svg.push_str(&format!(r#"<g id="g{i}" opacity="0.9">"#));
+        }
+        svg.push_str(r#"<rect width="10" height="10"/>"#);
+        for _ in 0..depth {
+            svg.push_str("</g>");
+        }
+        svg.push_str("</svg>");
+
+        let rendered = vello_svg::render(&svg).unwrap();
+        assert_eq!(rendered.stats.paths, 1, "nesting isn't limited by default");
+        assert_eq!(rendered.stats.layers, depth, "every group is drawn");
+
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            limits: Limits::UNTRUSTED,
+            ..Default::default()
+        };
+        let result = vello_svg::render_tree_with_options(&tree, &options, &mut StrictErrorHandler);
+        assert!(
+            matches!(
+                &result,
+                Err(Error::LimitExceeded {
+                    limit: Limit::NestingDepth,
+                    ..
+                })
+            ),
+            "expected a nesting depth error, got {result:?}"
+        );
+        let mut scene = Scene::new();
+        vello_svg::append_tree_with_options(&mut scene, &tree, &options, &mut DefaultErrorHandler)
+            .unwrap_err();
+        assert_eq!(
+            scene.encoding().n_open_clips,
+            0,
+            "truncated output should still pop all layers"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::SvgScene")]
+#[cfg(test)]
+pub mod svg_scene_test {
+    use vello_svg::vello::Scene;
+    use vello_svg::vello::kurbo::Affine;
+    use vello_svg::{SvgScene, usvg};
+
+    #[test]
+    fn same_as_append_tree() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+            <g opacity="0.5" transform="rotate(10)">
+                <rect width="10" height="10" fill="red" stroke="blue"/>
+            </g>
+            <circle cx="15" cy="15" r="5"/>
+        </svg>"#;
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+
+        let mut direct = Scene::new();
+        vello_svg::append_tree(&mut direct, &tree);
+        let encoded = SvgScene::new(&tree);
+        let mut appended = Scene::new();
+        encoded.clone().append_to(&mut appended, Affine::IDENTITY);
+
+        let (direct, appended) = (direct.encoding(), appended.encoding());
+        assert!(direct.draw_tags == appended.draw_tags, "same draws");
+        assert_eq!(direct.draw_data, appended.draw_data, "same draw data");
+        assert_eq!(direct.path_data, appended.path_data, "same paths");
+        assert_eq!(direct.n_paths, appended.n_paths, "same number of paths");
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SvgScene>();
+    }
+}
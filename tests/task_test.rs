@@ -0,0 +1,125 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RenderTask")]
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub mod task_test {
+    use std::fmt::Write as _;
+    use std::time::Duration;
+    use vello_svg::{Error, RenderOptions, RenderTask, usvg};
+
+    /// Render `svg`, which is already loaded, with default options.
+    fn render_async(svg: String) -> RenderTask {
+        vello_svg::render_async(
+            move || Ok::<_, Error>(svg),
+            usvg::Options::default(),
+            RenderOptions::default(),
+        )
+    }
+
+    /// A document with `n` squares.
+    fn squares(n: usize) -> String {
+        let mut svg =
+            String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">"#);
+        for i in 0..n {
+            write!(svg, r#"<rect x="{}" width="1" height="1"/>"#, i % 100).unwrap();
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    #[test]
+    fn same_as_render() {
+        let svg = squares(1000);
+        let task = render_async(svg.clone());
+        let rendered = task.wait().unwrap();
+        let expected = vello_svg::render(&svg).unwrap();
+        assert_eq!(rendered.size, expected.size, "the size of the document");
+        assert_eq!(
+            rendered.scene.encoding().path_data,
+            expected.scene.encoding().path_data,
+            "the same paths are encoded"
+        );
+        assert_eq!(rendered.stats.paths, 1000, "all squares are drawn");
+        assert!(
+            rendered.stats.parse_time.is_some(),
+            "the document was parsed on the background thread"
+        );
+    }
+
+    #[test]
+    fn progress() {
+        let mut task = render_async(squares(10));
+        let result = loop {
+            let progress = task.progress();
+            assert!((0.0..=1.0).contains(&progress), "progress is a fraction");
+            if let Some(result) = task.take_timeout(Duration::from_millis(10)) {
+                break result;
+            }
+        };
+        assert!(result.is_ok(), "the document is rendered");
+        assert_eq!(task.progress(), 1.0, "the task is finished");
+        assert!(task.try_take().is_none(), "the result was taken");
+    }
+
+    #[test]
+    fn cancel() {
+        let task = render_async(squares(10_000));
+        task.cancel();
+        assert!(
+            matches!(task.wait(), Err(Error::Cancelled)),
+            "the task stopped before finishing"
+        );
+    }
+
+    #[test]
+    fn invalid_document() {
+        let task = render_async("<svg".to_owned());
+        assert!(
+            matches!(task.wait(), Err(Error::Svg(_))),
+            "the parse error is delivered"
+        );
+    }
+
+    #[test]
+    fn load_file() {
+        let dir =
+            std::env::temp_dir().join(format!("vello_svg-render_async-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("squares.svg"), squares(10)).unwrap();
+        std::fs::write(
+            dir.join("nested.svg"),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <image width="10" height="10" href="squares.svg"/>
+            </svg>"#,
+        )
+        .unwrap();
+        let opt = usvg::Options {
+            resources_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let path = dir.join("nested.svg");
+        let task =
+            vello_svg::render_async(move || std::fs::read(path), opt, RenderOptions::default());
+        let result = task.wait();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            result.unwrap().stats.paths,
+            10,
+            "the relative image is resolved with the usvg options"
+        );
+    }
+
+    #[test]
+    fn load_error() {
+        let task = vello_svg::render_async(
+            || std::fs::read("this/file/does/not/exist.svg"),
+            usvg::Options::default(),
+            RenderOptions::default(),
+        );
+        assert!(
+            matches!(task.wait(), Err(Error::Io(_))),
+            "the error of the loader is delivered"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![allow(missing_docs, reason = "tests for vello_svg::RenderOptions::viewport")]
+#[cfg(test)]
+pub mod viewport_test {
+    use std::fmt::Write as _;
+    use vello_svg::vello::kurbo::Rect;
+    use vello_svg::{DefaultErrorHandler, RenderOptions, RenderStats, usvg};
+
+    fn stats(svg: &str, viewport: Rect) -> RenderStats {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let options = RenderOptions {
+            viewport: Some(viewport),
+            ..Default::default()
+        };
+        vello_svg::render_tree_with_options(&tree, &options, &mut DefaultErrorHandler)
+            .unwrap()
+            .stats
+    }
+
+    /// A 10 by 10 grid of squares, 10 units apart.
+    fn grid() -> String {
+        let mut svg =
+            String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">"#);
+        for y in 0..10 {
+            for x in 0..10 {
+                write!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="5" height="5"/>"#,
+                    x * 10,
+                    y * 10
+                )
+                .unwrap();
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    #[test]
+    fn culls_outside_nodes() {
+        let visible = stats(&grid(), Rect::new(0.0, 0.0, 25.0, 25.0));
+        assert_eq!(visible.paths, 9, "the squares in the top left corner");
+        assert_eq!(visible.culled_nodes, 91, "the other squares");
+
+        let outside = stats(&grid(), Rect::new(200.0, 200.0, 300.0, 300.0));
+        assert_eq!(outside.paths, 0, "nothing is visible");
+    }
+
+    #[test]
+    fn keeps_strokes_and_filters() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <filter id="blur" filterUnits="userSpaceOnUse" x="-10" y="-10" width="120" height="120">
+                <feGaussianBlur stdDeviation="5"/>
+            </filter>
+            <rect x="30" width="5" height="5" stroke="black" stroke-width="30"/>
+            <g filter="url(#blur)">
+                <rect y="50" width="5" height="5"/>
+            </g>
+            <rect x="80" y="80" width="5" height="5"/>
+        </svg>"#;
+        let visible = stats(svg, Rect::new(0.0, 0.0, 20.0, 20.0));
+        assert_eq!(
+            visible.culled_nodes, 2,
+            "the far square, and the content of the filtered group, as filters aren't drawn"
+        );
+        assert_eq!(
+            visible.paths, 2,
+            "the fill and stroke of the square whose stroke reaches into the viewport"
+        );
+        assert_eq!(visible.groups, 2, "the root and the filtered group");
+    }
+}